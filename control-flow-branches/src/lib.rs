@@ -0,0 +1,9 @@
+/*
+ * Control flow branches
+ * The temperature code lives here rather than in main.rs, so the examples and the
+ * convert, table and analyze commands share one implementation.
+ */
+
+pub mod readings;
+pub mod table;
+pub mod temperature;
@@ -1,4 +1,24 @@
+use control_flow_branches::{
+    readings::{self, Analysis, Options},
+    table::{self, Format, Range},
+    temperature::{Scale, Temperature},
+};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader, BufWriter},
+    process,
+};
+
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("convert") => return convert(&args[1..]),
+        Some("table") => return conversion_table(&args[1..]),
+        Some("analyze") => return analyze(&args[1..]),
+        _ => {}
+    }
+
     /* If are sometimes called arms, just like the arms in match expressions */
     let number = 5;
 
@@ -126,13 +146,215 @@ fn main() {
 
 fn temp_conversion() {
     // (1°C × 9/5) + 32 = 33.8°F
-    // (1°F − 32) × 5/9 = -17.22°C
-    let temp_in_deg_celcius = 1;
-    let temp_in_fahrenheit = 33;
-    let deg_celcius_to_far = (temp_in_deg_celcius * 9 / 5) + 32;
-    let deg_far_to_celcius = (temp_in_fahrenheit - 32) * 5 / 9;
-    println!("The temp is F is: {deg_celcius_to_far}F");
-    println!("The temp in C is: {deg_far_to_celcius}");
+    // (33°F − 32) × 5/9 = 0.56°C
+    // Integer math would round these to 33°F and 0°C, so this goes through f64
+    let temp_in_deg_celcius = Temperature::celsius(1.0).expect("1°C is above absolute zero");
+    let temp_in_fahrenheit = Temperature::fahrenheit(33.0).expect("33°F is above absolute zero");
+    let deg_celcius_to_far = temp_in_deg_celcius.to(Scale::Fahrenheit);
+    let deg_far_to_celcius = temp_in_fahrenheit.to(Scale::Celsius);
+    println!("The temp is F is: {deg_celcius_to_far:.2}");
+    println!("The temp in C is: {deg_far_to_celcius:.2}");
+}
+
+/// `convert 98.6F to C [--decimals N]`, without a target every scale is printed.
+fn convert(args: &[String]) {
+    let mut decimals = 2;
+    let mut words = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--decimals" | "-d" => {
+                let value = args
+                    .next()
+                    .unwrap_or_else(|| fail("--decimals needs a value"));
+                decimals = value
+                    .parse()
+                    .unwrap_or_else(|_| fail(&format!("Invalid --decimals value `{value}`")));
+            }
+            _ => words.push(arg.as_str()),
+        }
+    }
+
+    // Everything before `to` is the temperature, so `98.6 F` and `98.6F` both work
+    let split = words
+        .iter()
+        .position(|word| matches!(*word, "to" | "in"))
+        .unwrap_or(words.len());
+    let (source, target) = words.split_at(split);
+    if source.is_empty() {
+        fail("Usage: convert <temperature> [to <scale>] [--decimals N], e.g. convert 98.6F to C");
+    }
+
+    let temperature = source
+        .concat()
+        .parse::<Temperature>()
+        .unwrap_or_else(|e| fail(&e.to_string()));
+    let targets = match target {
+        [] => Scale::ALL
+            .into_iter()
+            .filter(|&scale| scale != temperature.scale())
+            .collect(),
+        [_, scale] => vec![scale
+            .parse::<Scale>()
+            .unwrap_or_else(|e| fail(&e.to_string()))],
+        _ => fail("Expected one scale after `to`, e.g. convert 98.6F to C"),
+    };
+
+    for scale in targets {
+        println!(
+            "{temperature:.decimals$} = {:.decimals$}",
+            temperature.to(scale)
+        );
+    }
+}
+
+/// `table -40C to 100C every 5 [--scales C,F,K] [--format text|csv|markdown|html] [--decimals N]`
+fn conversion_table(args: &[String]) {
+    let usage = "Usage: table <start> to <end> [every <step>] [--scales C,F,K] [--format text|csv|markdown|html] [--decimals N]";
+    let mut decimals = 2;
+    let mut format = Format::Text;
+    let mut scales = Vec::new();
+    let mut words = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("{arg} needs a value")))
+        };
+        match arg.as_str() {
+            "--decimals" | "-d" => {
+                let value = value();
+                decimals = value
+                    .parse()
+                    .unwrap_or_else(|_| fail(&format!("Invalid --decimals value `{value}`")));
+            }
+            "--format" | "-f" => {
+                format = value()
+                    .parse()
+                    .unwrap_or_else(|e: table::TableError| fail(&e.to_string()));
+            }
+            "--scales" | "-s" => {
+                for scale in value().split(',') {
+                    scales.push(
+                        scale
+                            .parse::<Scale>()
+                            .unwrap_or_else(|e| fail(&e.to_string())),
+                    );
+                }
+            }
+            _ => words.push(arg.as_str()),
+        }
+    }
+
+    let (start, rest) = match words.iter().position(|word| *word == "to") {
+        Some(to) => (&words[..to], &words[to + 1..]),
+        None => fail(usage),
+    };
+    let (end, step) = match rest
+        .iter()
+        .position(|word| matches!(*word, "every" | "step"))
+    {
+        Some(every) => (&rest[..every], rest[every + 1..].concat()),
+        None => (rest, "1".to_string()),
+    };
+
+    let start = start
+        .concat()
+        .parse::<Temperature>()
+        .unwrap_or_else(|e| fail(&e.to_string()));
+    // The end is in the scale of the start unless it says otherwise
+    let end = end.concat();
+    let end = match end.parse::<f64>() {
+        Ok(value) => Temperature::new(value, start.scale()),
+        Err(_) => end.parse(),
+    }
+    .unwrap_or_else(|e| fail(&e.to_string()));
+    let step = step
+        .parse()
+        .unwrap_or_else(|_| fail(&format!("Invalid step `{step}`")));
+    let range = Range::new(start, end, step).unwrap_or_else(|e| fail(&e.to_string()));
+
+    if scales.is_empty() {
+        scales.push(start.scale());
+        scales.extend(
+            Scale::ALL
+                .into_iter()
+                .filter(|&scale| scale != start.scale()),
+        );
+    }
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    match table::write_table(&mut out, &range, &scales, format, decimals) {
+        // `table ... | head` closes the pipe early, that is not an error
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => fail(&e.to_string()),
+        _ => {}
+    }
+}
+
+/// `analyze <log.csv|-> [--unit C] [--interval 15m] [--above 30C] [--below 0C] [--decimals N]`
+fn analyze(args: &[String]) {
+    let mut decimals = 2;
+    let mut path = None;
+    let mut options = Options {
+        scale: Scale::Celsius,
+        interval: None,
+        above: None,
+        below: None,
+    };
+    let temperature = |value: &str| {
+        value
+            .parse::<Temperature>()
+            .unwrap_or_else(|e| fail(&e.to_string()))
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("{arg} needs a value")))
+        };
+        match arg.as_str() {
+            "--decimals" | "-d" => {
+                let value = value();
+                decimals = value
+                    .parse()
+                    .unwrap_or_else(|_| fail(&format!("Invalid --decimals value `{value}`")));
+            }
+            "--unit" | "-u" => {
+                options.scale = value().parse().unwrap_or_else(
+                    |e: control_flow_branches::temperature::TemperatureError| fail(&e.to_string()),
+                );
+            }
+            "--interval" | "-i" => {
+                let value = value();
+                options.interval = Some(readings::parse_interval(value).unwrap_or_else(|| {
+                    fail(&format!(
+                        "Invalid --interval `{value}`, expected e.g. 90s, 15m or 1h"
+                    ))
+                }));
+            }
+            "--above" => options.above = Some(temperature(value())),
+            "--below" => options.below = Some(temperature(value())),
+            _ if path.is_none() => path = Some(arg.as_str()),
+            _ => fail(&format!("Unexpected argument `{arg}`")),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| {
+        fail("Usage: analyze <log.csv|-> [--unit C] [--interval 15m] [--above 30C] [--below 0C] [--decimals N]")
+    });
+    let log = match path {
+        "-" => readings::read_log(io::stdin().lock()),
+        path => File::open(path).and_then(|file| readings::read_log(BufReader::new(file))),
+    };
+    let (log, malformed) = log.unwrap_or_else(|e| fail(&format!("Could not read {path}: {e}")));
+
+    print!("{:.decimals$}", Analysis::new(&log, malformed, &options));
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(2);
 }
 
 fn nth_fibonacci_number(n: i32) -> i32 {
@@ -140,7 +362,7 @@ fn nth_fibonacci_number(n: i32) -> i32 {
         return n;
     }
 
-    return nth_fibonacci_number(n - 1) + nth_fibonacci_number(n - 2);
+    nth_fibonacci_number(n - 1) + nth_fibonacci_number(n - 2)
 }
 
 fn twevle_days_of_christmas() {
@@ -187,4 +409,4 @@ fn twevle_days_of_christmas() {
 
         println!();
     }
-}
\ No newline at end of file
+}
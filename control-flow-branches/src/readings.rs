@@ -0,0 +1,713 @@
+/*
+ * Sensor logs, a CSV of `timestamp,reading` rows where every reading carries its own unit,
+ * like `2024-03-01T06:00:00Z,21.5C` next to `2024-03-01T06:15:00Z,70.9F`. Everything is
+ * converted to one scale before it is summed up. A row that does not parse is kept aside
+ * with its line number and the rest of the file is still read.
+ *
+ * Timestamps are taken as they are written, without time zones, so days are the days of
+ * the clock that wrote the log.
+ */
+
+use crate::temperature::{Scale, Temperature, TemperatureError};
+use std::{
+    fmt,
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Seconds since 1970-01-01 00:00:00.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn date(&self) -> Date {
+        Date::from_days(self.0.div_euclid(SECONDS_PER_DAY))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.0.rem_euclid(SECONDS_PER_DAY);
+        write!(
+            f,
+            "{} {:02}:{:02}:{:02}",
+            self.date(),
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = RowError;
+
+    /// `2024-03-01T06:15:00Z`, `2024-03-01 06:15` or a bare date for midnight.
+    /// Fractions of a second are dropped.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RowError::InvalidTimestamp(s.to_string());
+        let text = s.trim().trim_end_matches(['Z', 'z']);
+        let (date, time) = match text.split_once(['T', ' ']) {
+            Some((date, time)) => (date, time),
+            None => (text, "00:00"),
+        };
+
+        let date: Date = date.parse().map_err(|_| invalid())?;
+        let time = time.split('.').next().unwrap_or(time);
+        let mut parts = time.split(':').map(|part| part.parse::<i64>().ok());
+        let (hour, minute, second) = match (parts.next(), parts.next(), parts.next(), parts.next())
+        {
+            (Some(Some(hour)), Some(Some(minute)), None, None) => (hour, minute, 0),
+            (Some(Some(hour)), Some(Some(minute)), Some(Some(second)), None) => {
+                (hour, minute, second)
+            }
+            _ => return Err(invalid()),
+        };
+        if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..60).contains(&second) {
+            return Err(invalid());
+        }
+
+        // Years far out of range do not fit in an i64 of seconds
+        date.days()
+            .and_then(|days| days.checked_mul(SECONDS_PER_DAY))
+            .and_then(|seconds| seconds.checked_add(hour * 3600 + minute * 60 + second))
+            .map(Timestamp)
+            .ok_or_else(invalid)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// The civil date `days` after 1970-01-01, after Howard Hinnant's `civil_from_days`.
+    pub fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01, the inverse of `from_days`. `None` if that does not fit.
+    pub fn days(&self) -> Option<i64> {
+        let year = self.year.checked_sub(i64::from(self.month <= 2))?;
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era.checked_mul(146_097)?
+            .checked_add(doe)?
+            .checked_sub(719_468)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = RowError;
+
+    /// `YYYY-MM-DD`, real calendar days only.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RowError::InvalidTimestamp(s.to_string());
+        let mut parts = s.split('-');
+        let (Some(year), Some(month), Some(day), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let date = Date {
+            year: year.parse().map_err(|_| invalid())?,
+            month: month.parse().map_err(|_| invalid())?,
+            day: day.parse().map_err(|_| invalid())?,
+        };
+        // A day like 02-30 comes back as another date
+        if date.days().map(Date::from_days) != Some(date) {
+            return Err(invalid());
+        }
+        Ok(date)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowError {
+    /// Rows need a timestamp and a reading, and nothing else.
+    WrongFieldCount(usize),
+    InvalidTimestamp(String),
+    Temperature(TemperatureError),
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RowError::WrongFieldCount(count) => {
+                write!(f, "expected 2 fields, timestamp and reading, found {count}")
+            }
+            RowError::InvalidTimestamp(text) => write!(f, "`{text}` is not a timestamp"),
+            RowError::Temperature(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for RowError {}
+
+impl From<TemperatureError> for RowError {
+    fn from(e: TemperatureError) -> Self {
+        RowError::Temperature(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading {
+    /// Line in the file, counting from 1.
+    pub line: usize,
+    pub time: Timestamp,
+    pub temperature: Temperature,
+}
+
+/// A row that was skipped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Malformed {
+    pub line: usize,
+    pub error: RowError,
+}
+
+/// Every reading in file order and every row that was skipped. Blank lines, `#` comments
+/// and a header line are ignored. Only failing to read at all is an error.
+pub fn read_log(reader: impl BufRead) -> io::Result<(Vec<Reading>, Vec<Malformed>)> {
+    let mut readings = Vec::new();
+    let mut malformed = Vec::new();
+    let mut first = true;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let row = line.trim();
+        if row.is_empty() || row.starts_with('#') {
+            continue;
+        }
+        if std::mem::take(&mut first) && is_header(row) {
+            continue;
+        }
+
+        match parse_row(row) {
+            Ok((time, temperature)) => readings.push(Reading {
+                line: index + 1,
+                time,
+                temperature,
+            }),
+            Err(error) => malformed.push(Malformed {
+                line: index + 1,
+                error,
+            }),
+        }
+    }
+
+    Ok((readings, malformed))
+}
+
+/// Names like `time,reading` and not a data row with a broken timestamp, that one is reported.
+fn is_header(row: &str) -> bool {
+    let mut fields = row
+        .split(',')
+        .map(|field| field.trim().trim_matches('"').trim());
+    let starts_with_a_word = fields
+        .next()
+        .is_some_and(|name| name.starts_with(char::is_alphabetic));
+    starts_with_a_word
+        && fields
+            .next()
+            .is_some_and(|name| name.parse::<Temperature>().is_err())
+}
+
+fn parse_row(row: &str) -> Result<(Timestamp, Temperature), RowError> {
+    let fields: Vec<&str> = row
+        .split(',')
+        .map(|field| field.trim().trim_matches('"').trim())
+        .collect();
+    let [time, reading] = fields[..] else {
+        return Err(RowError::WrongFieldCount(fields.len()));
+    };
+
+    Ok((time.parse()?, reading.parse()?))
+}
+
+/// Parses `900`, `90s`, `15m`, `2h` or `1d` into seconds.
+pub fn parse_interval(s: &str) -> Option<i64> {
+    let s = s.trim();
+    let (number, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let seconds = match unit {
+        "" | "s" => 1,
+        "m" | "min" => 60,
+        "h" => 3600,
+        "d" => SECONDS_PER_DAY,
+        _ => return None,
+    };
+
+    number
+        .parse::<i64>()
+        .ok()
+        .and_then(|number| number.checked_mul(seconds))
+        .filter(|&interval| interval > 0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DaySummary {
+    pub date: Date,
+    pub readings: usize,
+    pub min: Temperature,
+    pub max: Temperature,
+    pub mean: Temperature,
+}
+
+/// Readings that should have been there but were not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    /// The last reading before the gap and the first one after it.
+    pub from: Timestamp,
+    pub to: Timestamp,
+    pub missing: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Threshold {
+    Above,
+    Below,
+}
+
+/// A run of consecutive readings past a threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Alert {
+    pub threshold: Threshold,
+    pub limit: Temperature,
+    pub from: Timestamp,
+    pub to: Timestamp,
+    pub readings: usize,
+    /// The hottest reading of an `Above` alert, the coldest of a `Below` one.
+    pub peak: Temperature,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Options {
+    /// The scale of every number in the report.
+    pub scale: Scale,
+    /// Seconds between readings, the most common gap in the log if not given.
+    pub interval: Option<i64>,
+    pub above: Option<Temperature>,
+    pub below: Option<Temperature>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub scale: Scale,
+    pub readings: usize,
+    pub interval: Option<i64>,
+    pub days: Vec<DaySummary>,
+    pub gaps: Vec<Gap>,
+    pub alerts: Vec<Alert>,
+    pub malformed: Vec<Malformed>,
+}
+
+impl Analysis {
+    /// `readings` may be in any order and any scale.
+    pub fn new(readings: &[Reading], malformed: Vec<Malformed>, options: &Options) -> Self {
+        let scale = options.scale;
+        let mut readings: Vec<(Timestamp, Temperature)> = readings
+            .iter()
+            .map(|reading| (reading.time, reading.temperature.to(scale)))
+            .collect();
+        readings.sort_by_key(|&(time, _)| time);
+
+        let interval = options.interval.or_else(|| common_interval(&readings));
+        Self {
+            scale,
+            readings: readings.len(),
+            interval,
+            days: days(&readings),
+            gaps: interval.map_or_else(Vec::new, |interval| gaps(&readings, interval)),
+            alerts: [
+                options.above.map(|limit| (Threshold::Above, limit)),
+                options.below.map(|limit| (Threshold::Below, limit)),
+            ]
+            .into_iter()
+            .flatten()
+            .flat_map(|(threshold, limit)| alerts(&readings, threshold, limit.to(scale)))
+            .collect(),
+            malformed,
+        }
+    }
+}
+
+/// The gap that occurs most often, the shortest one on a tie.
+fn common_interval(readings: &[(Timestamp, Temperature)]) -> Option<i64> {
+    let mut steps: Vec<i64> = readings
+        .windows(2)
+        .map(|pair| pair[1].0 .0.saturating_sub(pair[0].0 .0))
+        .filter(|&step| step > 0)
+        .collect();
+    steps.sort_unstable();
+
+    steps
+        .chunk_by(|a, b| a == b)
+        .max_by_key(|run| (run.len(), -run[0]))
+        .map(|run| run[0])
+}
+
+fn days(readings: &[(Timestamp, Temperature)]) -> Vec<DaySummary> {
+    readings
+        .chunk_by(|a, b| a.0.date() == b.0.date())
+        .map(|day| {
+            let values = day.iter().map(|(_, temperature)| temperature.value());
+            let scale = day[0].1.scale();
+            let temperature =
+                |value| Temperature::new(value, scale).expect("made from valid readings");
+
+            DaySummary {
+                date: day[0].0.date(),
+                readings: day.len(),
+                min: temperature(values.clone().fold(f64::INFINITY, f64::min)),
+                max: temperature(values.clone().fold(f64::NEG_INFINITY, f64::max)),
+                mean: temperature(values.sum::<f64>() / day.len() as f64),
+            }
+        })
+        .collect()
+}
+
+fn gaps(readings: &[(Timestamp, Temperature)], interval: i64) -> Vec<Gap> {
+    readings
+        .windows(2)
+        .filter_map(|pair| {
+            let (from, to) = (pair[0].0, pair[1].0);
+            // Half an interval of slack, sensors are rarely on the second
+            let span = to.0.saturating_sub(from.0).saturating_add(interval / 2);
+            let missing = span / interval - 1;
+            (missing > 0).then_some(Gap { from, to, missing })
+        })
+        .collect()
+}
+
+fn alerts(
+    readings: &[(Timestamp, Temperature)],
+    threshold: Threshold,
+    limit: Temperature,
+) -> Vec<Alert> {
+    let past = |temperature: &Temperature| match threshold {
+        Threshold::Above => temperature.value() > limit.value(),
+        Threshold::Below => temperature.value() < limit.value(),
+    };
+
+    readings
+        .chunk_by(|a, b| past(&a.1) == past(&b.1))
+        .filter(|run| past(&run[0].1))
+        .map(|run| {
+            let temperatures = run.iter().map(|(_, temperature)| *temperature);
+            Alert {
+                threshold,
+                limit,
+                from: run[0].0,
+                to: run[run.len() - 1].0,
+                readings: run.len(),
+                peak: match threshold {
+                    Threshold::Above => temperatures.max_by(|a, b| a.value().total_cmp(&b.value())),
+                    Threshold::Below => temperatures.min_by(|a, b| a.value().total_cmp(&b.value())),
+                }
+                .expect("runs are never empty"),
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Analysis {
+    /// The precision is the number of decimals, like for `Temperature`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let decimals = f.precision().unwrap_or(2);
+        let skipped = self.malformed.len();
+        writeln!(
+            f,
+            "{} readings in {}, {skipped} malformed rows skipped",
+            self.readings,
+            self.scale.name()
+        )?;
+
+        if !self.days.is_empty() {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{:<10}  {:>8}  {:>10}  {:>10}  {:>10}",
+                "Day", "Readings", "Min", "Max", "Mean"
+            )?;
+        }
+        for day in &self.days {
+            writeln!(
+                f,
+                "{:<10}  {:>8}  {:>10}  {:>10}  {:>10}",
+                day.date.to_string(),
+                day.readings,
+                format!("{:.decimals$}", day.min),
+                format!("{:.decimals$}", day.max),
+                format!("{:.decimals$}", day.mean)
+            )?;
+        }
+
+        writeln!(f)?;
+        match self.interval {
+            Some(interval) if self.gaps.is_empty() => {
+                writeln!(f, "No missing readings at one every {interval}s")?
+            }
+            Some(interval) => {
+                let missing = self
+                    .gaps
+                    .iter()
+                    .fold(0i64, |total, gap| total.saturating_add(gap.missing));
+                writeln!(
+                    f,
+                    "{missing} missing readings at one every {interval}s, in {} gaps",
+                    self.gaps.len()
+                )?;
+                for gap in &self.gaps {
+                    writeln!(f, "  {} to {}, {} missing", gap.from, gap.to, gap.missing)?;
+                }
+            }
+            None => writeln!(f, "Too few readings to tell the interval")?,
+        }
+
+        if !self.alerts.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Alerts")?;
+        }
+        for alert in &self.alerts {
+            let (direction, peak) = match alert.threshold {
+                Threshold::Above => ("above", "peak"),
+                Threshold::Below => ("below", "low"),
+            };
+            writeln!(
+                f,
+                "  {direction} {:.decimals$} from {} to {}, {} readings, {peak} {:.decimals$}",
+                alert.limit, alert.from, alert.to, alert.readings, alert.peak
+            )?;
+        }
+
+        if !self.malformed.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Malformed rows")?;
+        }
+        for row in &self.malformed {
+            writeln!(f, "  line {}: {}", row.line, row.error)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+time,reading
+2024-03-01T00:00:00Z,20C
+2024-03-01T00:15:00Z,26C
+2024-03-01T00:45:00Z,80.6F
+bad,20C
+2024-03-01T01:06:40Z,20C
+2024-02-30T00:00:00Z,20C
+# the sensor was moved here
+2024-03-01T01:45:00Z,30C
+2024-03-01T01:50:00Z
+";
+
+    fn analyze(log: &str, options: &Options) -> Analysis {
+        let (readings, malformed) = read_log(log.as_bytes()).unwrap();
+        Analysis::new(&readings, malformed, options)
+    }
+
+    fn at(text: &str) -> Timestamp {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn only_real_calendar_days_are_dates() {
+        assert_eq!(
+            "2024-02-29".parse(),
+            Ok(Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            })
+        );
+        for text in [
+            "2024-02-30",
+            "2023-02-29",
+            "2024-13-01",
+            "2024-00-10",
+            "2024-1",
+        ] {
+            assert!(text.parse::<Date>().is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn timestamps_that_do_not_fit_an_i64_are_invalid() {
+        assert_eq!(at("1970-01-02"), Timestamp(SECONDS_PER_DAY));
+        assert_eq!(at("1969-12-31T23:59:59Z"), Timestamp(-1));
+        assert_eq!(at("2024-03-01 06:15").to_string(), "2024-03-01 06:15:00");
+
+        for text in [
+            "300000000000-01-01T00:00:00Z",
+            "-300000000000-01-01",
+            "9223372036854775807-01-01",
+            "2024-03-01T24:00:00Z",
+        ] {
+            assert_eq!(
+                text.parse::<Timestamp>(),
+                Err(RowError::InvalidTimestamp(text.to_string())),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_rows_are_reported_by_line() {
+        let (readings, malformed) = read_log(LOG.as_bytes()).unwrap();
+        assert_eq!(
+            readings
+                .iter()
+                .map(|reading| reading.line)
+                .collect::<Vec<_>>(),
+            [2, 3, 4, 6, 9]
+        );
+        assert_eq!(
+            malformed,
+            [
+                Malformed {
+                    line: 5,
+                    error: RowError::InvalidTimestamp(String::from("bad"))
+                },
+                Malformed {
+                    line: 7,
+                    error: RowError::InvalidTimestamp(String::from("2024-02-30T00:00:00Z"))
+                },
+                Malformed {
+                    line: 10,
+                    error: RowError::WrongFieldCount(1)
+                },
+            ]
+        );
+
+        // Without a header the first row is data, and a broken one is reported too
+        let (_, malformed) = read_log("bad,20C\n2024-03-01,20C\n".as_bytes()).unwrap();
+        assert_eq!(malformed.len(), 1);
+        assert_eq!(malformed[0].line, 1);
+    }
+
+    #[test]
+    fn gaps_allow_half_an_interval_of_slack() {
+        let options = Options {
+            scale: Scale::Celsius,
+            interval: Some(900),
+            above: None,
+            below: None,
+        };
+        let analysis = analyze(LOG, &options);
+
+        // 30 minutes is one missing, 21:40 is within the slack, 38:20 is two missing
+        assert_eq!(
+            analysis.gaps,
+            [
+                Gap {
+                    from: at("2024-03-01T00:15:00Z"),
+                    to: at("2024-03-01T00:45:00Z"),
+                    missing: 1
+                },
+                Gap {
+                    from: at("2024-03-01T01:06:40Z"),
+                    to: at("2024-03-01T01:45:00Z"),
+                    missing: 2
+                },
+            ]
+        );
+        assert_eq!(analysis.days.len(), 1);
+        assert_eq!(analysis.days[0].readings, 5);
+
+        let guessed = analyze(
+            LOG,
+            &Options {
+                interval: None,
+                ..options
+            },
+        );
+        assert_eq!(guessed.interval, Some(900));
+    }
+
+    #[test]
+    fn alerts_cover_each_run_past_the_threshold() {
+        let options = Options {
+            scale: Scale::Celsius,
+            interval: None,
+            above: Some(Temperature::celsius(25.0).unwrap()),
+            below: Some(Temperature::fahrenheit(69.8).unwrap()),
+        };
+        let alerts = analyze(LOG, &options).alerts;
+
+        let runs: Vec<(Threshold, Timestamp, Timestamp, usize)> = alerts
+            .iter()
+            .map(|alert| (alert.threshold, alert.from, alert.to, alert.readings))
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                (
+                    Threshold::Above,
+                    at("2024-03-01T00:15:00Z"),
+                    at("2024-03-01T00:45:00Z"),
+                    2
+                ),
+                (
+                    Threshold::Above,
+                    at("2024-03-01T01:45:00Z"),
+                    at("2024-03-01T01:45:00Z"),
+                    1
+                ),
+                // A reading back above the limit ends the run
+                (
+                    Threshold::Below,
+                    at("2024-03-01T00:00:00Z"),
+                    at("2024-03-01T00:00:00Z"),
+                    1
+                ),
+                (
+                    Threshold::Below,
+                    at("2024-03-01T01:06:40Z"),
+                    at("2024-03-01T01:06:40Z"),
+                    1
+                ),
+            ]
+        );
+        // 80.6°F is the peak of the first run, reported in the scale of the report
+        assert_eq!(alerts[0].peak.scale(), Scale::Celsius);
+        assert!((alerts[0].peak.value() - 27.0).abs() < 1e-9);
+        // 69.8°F is 21°C, the limit is compared in the scale of the report
+        assert!((alerts[2].limit.value() - 21.0).abs() < 1e-9);
+        assert_eq!(alerts[2].peak, Temperature::celsius(20.0).unwrap());
+    }
+}
@@ -0,0 +1,286 @@
+/*
+ * Conversion tables, one row per step from a start to an end temperature and one column
+ * per scale. Rows are computed and written one at a time, so a range with millions of steps
+ * never sits in memory. Text and Markdown still line up because every scale is a straight
+ * line, the widest cell of a column is always at one of the two ends of the range.
+ */
+
+use crate::temperature::{Scale, Temperature, TemperatureError};
+use std::{fmt, io::Write, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Csv,
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = TableError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(TableError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableError {
+    /// Steps must be finite and not zero.
+    InvalidStep(f64),
+    UnknownFormat(String),
+    Temperature(TemperatureError),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::InvalidStep(step) => write!(f, "{step} is not a usable step"),
+            TableError::UnknownFormat(name) => write!(
+                f,
+                "Unknown format `{name}`, expected text, csv, markdown or html"
+            ),
+            TableError::Temperature(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+impl From<TemperatureError> for TableError {
+    fn from(e: TemperatureError) -> Self {
+        TableError::Temperature(e)
+    }
+}
+
+/// From `start` to `end` in steps of `step`, all in the scale of `start`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Range {
+    start: Temperature,
+    end: Temperature,
+    step: f64,
+}
+
+impl Range {
+    /// `end` may be in any scale, the direction of `step` comes from the ends.
+    pub fn new(start: Temperature, end: Temperature, step: f64) -> Result<Self, TableError> {
+        if !step.is_finite() || step == 0.0 {
+            return Err(TableError::InvalidStep(step));
+        }
+
+        let end = end.to(start.scale());
+        Ok(Self {
+            start,
+            end,
+            step: step.abs().copysign(end.value() - start.value()),
+        })
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.start.scale()
+    }
+
+    pub fn start(&self) -> Temperature {
+        self.start
+    }
+
+    pub fn end(&self) -> Temperature {
+        self.end
+    }
+
+    /// How many rows the table has, the end itself is only a row when a step lands on it.
+    pub fn rows(&self) -> u64 {
+        let span = (self.end.value() - self.start.value()) / self.step;
+        // The slack keeps 0.3 / 0.1 = 2.9999999999999996 from losing the last row
+        ((span + 1e-9).floor() as u64).saturating_add(1)
+    }
+
+    /// Every step, computed from the start so long ranges do not pile up rounding errors.
+    pub fn temperatures(&self) -> impl Iterator<Item = Temperature> + '_ {
+        let (low, high) = if self.step > 0.0 {
+            (self.start.value(), self.end.value())
+        } else {
+            (self.end.value(), self.start.value())
+        };
+
+        (0..self.rows()).map(move |index| {
+            let value = (self.start.value() + index as f64 * self.step).clamp(low, high);
+            Temperature::new(value, self.scale()).expect("between two valid temperatures")
+        })
+    }
+}
+
+/// Writes the table row by row, `out` should be buffered for long ranges.
+pub fn write_table(
+    out: &mut impl Write,
+    range: &Range,
+    scales: &[Scale],
+    format: Format,
+    decimals: usize,
+) -> Result<(), std::io::Error> {
+    let cell = |temperature: Temperature, scale: Scale| {
+        let cell = format!("{:.decimals$}", temperature.to(scale).value());
+        // A rounding error just under zero would print -0.00 and widen the column
+        match cell.strip_prefix('-') {
+            Some(zero) if zero.chars().all(|c| c == '0' || c == '.') => zero.to_string(),
+            _ => cell,
+        }
+    };
+    let widths: Vec<usize> = scales
+        .iter()
+        .map(|&scale| {
+            [range.start(), range.end()]
+                .into_iter()
+                .map(|end| cell(end, scale).chars().count())
+                .chain([scale.symbol().chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    match format {
+        Format::Text => {
+            let header: Vec<String> = scales
+                .iter()
+                .zip(&widths)
+                .map(|(scale, &width)| format!("{:>width$}", scale.symbol()))
+                .collect();
+            writeln!(out, "{}", header.join("  "))?;
+        }
+        Format::Csv => {
+            let header: Vec<&str> = scales.iter().map(|scale| scale.name()).collect();
+            writeln!(out, "{}", header.join(","))?;
+        }
+        Format::Markdown => {
+            let header: Vec<String> = scales
+                .iter()
+                .zip(&widths)
+                .map(|(scale, &width)| format!("{:>width$}", scale.symbol()))
+                .collect();
+            let rule: Vec<String> = widths
+                .iter()
+                .map(|&width| format!("{}:", "-".repeat(width.max(2) - 1)))
+                .collect();
+            writeln!(out, "| {} |", header.join(" | "))?;
+            writeln!(out, "| {} |", rule.join(" | "))?;
+        }
+        Format::Html => {
+            writeln!(out, "<table>")?;
+            write!(out, "  <thead><tr>")?;
+            for scale in scales {
+                write!(out, "<th>{}</th>", scale.symbol())?;
+            }
+            writeln!(out, "</tr></thead>")?;
+            writeln!(out, "  <tbody>")?;
+        }
+    }
+
+    for temperature in range.temperatures() {
+        let cells = scales.iter().map(|&scale| cell(temperature, scale));
+        match format {
+            Format::Text | Format::Markdown => {
+                let cells: Vec<String> = cells
+                    .zip(&widths)
+                    .map(|(cell, &width)| format!("{cell:>width$}"))
+                    .collect();
+                if format == Format::Text {
+                    writeln!(out, "{}", cells.join("  "))?;
+                } else {
+                    writeln!(out, "| {} |", cells.join(" | "))?;
+                }
+            }
+            Format::Csv => writeln!(out, "{}", cells.collect::<Vec<_>>().join(","))?,
+            Format::Html => {
+                write!(out, "    <tr>")?;
+                for cell in cells {
+                    write!(out, "<td>{cell}</td>")?;
+                }
+                writeln!(out, "</tr>")?;
+            }
+        }
+    }
+
+    if format == Format::Html {
+        writeln!(out, "  </tbody>")?;
+        writeln!(out, "</table>")?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn celsius(value: f64) -> Temperature {
+        Temperature::celsius(value).unwrap()
+    }
+
+    fn values(range: &Range) -> Vec<f64> {
+        range.temperatures().map(|t| t.value()).collect()
+    }
+
+    #[test]
+    fn rows_include_an_end_that_a_step_lands_on() {
+        let range = Range::new(celsius(0.0), celsius(0.3), 0.1).unwrap();
+        assert_eq!(range.rows(), 4);
+        // 3 * 0.1 overshoots, the last row is clamped back to the end
+        assert_eq!(values(&range).last(), Some(&0.3));
+
+        let range = Range::new(celsius(0.0), celsius(10.0), 3.0).unwrap();
+        assert_eq!(values(&range), [0.0, 3.0, 6.0, 9.0]);
+
+        let single = Range::new(celsius(5.0), celsius(5.0), 1.0).unwrap();
+        assert_eq!(values(&single), [5.0]);
+    }
+
+    #[test]
+    fn reversed_ranges_count_down_whatever_the_sign_of_the_step() {
+        for step in [10.0, -10.0] {
+            let range = Range::new(celsius(100.0), celsius(0.0), step).unwrap();
+            assert_eq!(
+                values(&range),
+                [100.0, 90.0, 80.0, 70.0, 60.0, 50.0, 40.0, 30.0, 20.0, 10.0, 0.0]
+            );
+        }
+
+        // The end is converted to the scale of the start
+        let range =
+            Range::new(celsius(100.0), Temperature::fahrenheit(32.0).unwrap(), 50.0).unwrap();
+        assert_eq!(range.scale(), Scale::Celsius);
+        assert_eq!(range.rows(), 3);
+    }
+
+    #[test]
+    fn a_step_must_move() {
+        for step in [0.0, -0.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                Range::new(celsius(0.0), celsius(10.0), step),
+                Err(TableError::InvalidStep(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn csv_has_one_row_per_step() {
+        let range = Range::new(celsius(-40.0), celsius(0.0), 40.0).unwrap();
+        let mut out = Vec::new();
+        write_table(
+            &mut out,
+            &range,
+            &[Scale::Celsius, Scale::Fahrenheit],
+            Format::Csv,
+            1,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Celsius,Fahrenheit\n-40.0,-40.0\n0.0,32.0\n"
+        );
+    }
+}
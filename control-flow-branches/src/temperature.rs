@@ -0,0 +1,292 @@
+/*
+ * Temperatures in Celsius, Fahrenheit, Kelvin, Rankine and Réaumur, all in f64.
+ * Every scale is a straight line through Kelvin: kelvin = (value + offset) * factor.
+ * A temperature keeps the value and scale it was made with, so converting to the
+ * same scale gives back exactly what went in, and going there and back only loses
+ * the last bits of the f64.
+ */
+
+use std::{fmt, str::FromStr};
+
+/// Below this many Kelvin a reading is refused, the slack only absorbs rounding like -273.15°C.
+const ABSOLUTE_ZERO_SLACK: f64 = 1e-9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+    Reaumur,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 5] = [
+        Scale::Celsius,
+        Scale::Fahrenheit,
+        Scale::Kelvin,
+        Scale::Rankine,
+        Scale::Reaumur,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Scale::Celsius => "°C",
+            Scale::Fahrenheit => "°F",
+            Scale::Kelvin => "K",
+            Scale::Rankine => "°R",
+            Scale::Reaumur => "°Ré",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scale::Celsius => "Celsius",
+            Scale::Fahrenheit => "Fahrenheit",
+            Scale::Kelvin => "Kelvin",
+            Scale::Rankine => "Rankine",
+            Scale::Reaumur => "Réaumur",
+        }
+    }
+
+    /// `(offset, factor)` with kelvin = (value + offset) * factor.
+    fn line(&self) -> (f64, f64) {
+        match self {
+            Scale::Celsius => (273.15, 1.0),
+            Scale::Fahrenheit => (459.67, 5.0 / 9.0),
+            Scale::Kelvin => (0.0, 1.0),
+            Scale::Rankine => (0.0, 5.0 / 9.0),
+            Scale::Reaumur => (218.52, 1.25),
+        }
+    }
+
+    fn to_kelvin(self, value: f64) -> f64 {
+        let (offset, factor) = self.line();
+        (value + offset) * factor
+    }
+
+    fn value_of(self, kelvin: f64) -> f64 {
+        let (offset, factor) = self.line();
+        kelvin / factor - offset
+    }
+
+    /// Absolute zero in this scale.
+    pub fn absolute_zero(&self) -> f64 {
+        self.value_of(0.0)
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl FromStr for Scale {
+    type Err = TemperatureError;
+
+    /// Letters like `C` or `Re`, symbols like `°F` and full names, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().trim_start_matches('°').to_lowercase();
+        match name.as_str() {
+            "c" | "celsius" | "centigrade" => Ok(Scale::Celsius),
+            "f" | "fahrenheit" => Ok(Scale::Fahrenheit),
+            "k" | "kelvin" => Ok(Scale::Kelvin),
+            "r" | "ra" | "rankine" => Ok(Scale::Rankine),
+            "re" | "ré" | "reaumur" | "réaumur" => Ok(Scale::Reaumur),
+            _ => Err(TemperatureError::UnknownScale(s.trim().to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperatureError {
+    NotFinite,
+    BelowAbsoluteZero { value: f64, scale: Scale },
+    UnknownScale(String),
+    InvalidNumber(String),
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemperatureError::NotFinite => write!(f, "A temperature must be a finite number"),
+            TemperatureError::BelowAbsoluteZero { value, scale } => write!(
+                f,
+                "{value}{scale} is below absolute zero ({}{scale})",
+                scale.absolute_zero()
+            ),
+            TemperatureError::UnknownScale(name) => {
+                write!(f, "Unknown scale `{name}`, expected C, F, K, R or Re")
+            }
+            TemperatureError::InvalidNumber(text) => {
+                write!(f, "`{text}` does not start with a number")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemperatureError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Temperature {
+    value: f64,
+    scale: Scale,
+}
+
+impl Temperature {
+    /// Refuses NaN, infinities and anything colder than absolute zero.
+    pub fn new(value: f64, scale: Scale) -> Result<Self, TemperatureError> {
+        if !value.is_finite() {
+            return Err(TemperatureError::NotFinite);
+        }
+        if scale.to_kelvin(value) < -ABSOLUTE_ZERO_SLACK {
+            return Err(TemperatureError::BelowAbsoluteZero { value, scale });
+        }
+
+        Ok(Self { value, scale })
+    }
+
+    pub fn celsius(value: f64) -> Result<Self, TemperatureError> {
+        Self::new(value, Scale::Celsius)
+    }
+
+    pub fn fahrenheit(value: f64) -> Result<Self, TemperatureError> {
+        Self::new(value, Scale::Fahrenheit)
+    }
+
+    pub fn kelvin(value: f64) -> Result<Self, TemperatureError> {
+        Self::new(value, Scale::Kelvin)
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// The same temperature in another scale.
+    pub fn to(&self, scale: Scale) -> Self {
+        if scale == self.scale {
+            return *self;
+        }
+
+        // Rounding right at absolute zero must not produce -0.0000001 K
+        let kelvin = self.scale.to_kelvin(self.value).max(0.0);
+        Self {
+            value: scale.value_of(kelvin),
+            scale,
+        }
+    }
+
+    pub fn as_kelvin(&self) -> f64 {
+        self.to(Scale::Kelvin).value
+    }
+}
+
+impl fmt::Display for Temperature {
+    /// The precision is the number of decimals, `{:.1}` prints `37.0°C`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(decimals) => write!(f, "{:.*}{}", decimals, self.value, self.scale),
+            None => write!(f, "{}{}", self.value, self.scale),
+        }
+    }
+}
+
+impl FromStr for Temperature {
+    type Err = TemperatureError;
+
+    /// A number and a scale, like `98.6F`, `-40 °C` or `300 kelvin`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        // The number ends where the scale starts, exponents like 1e3 are not worth the ambiguity
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+            .unwrap_or(s.len());
+        let (number, scale) = s.split_at(split);
+
+        let value = number
+            .parse()
+            .map_err(|_| TemperatureError::InvalidNumber(s.to_string()))?;
+        Self::new(value, scale.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_scale_round_trips_through_every_other() {
+        let water_boils = Temperature::celsius(100.0).unwrap();
+        let expected = [100.0, 212.0, 373.15, 671.67, 80.0];
+        for (scale, value) in Scale::ALL.into_iter().zip(expected) {
+            assert!(
+                (water_boils.to(scale).value() - value).abs() < 1e-9,
+                "{scale}"
+            );
+        }
+
+        for from in Scale::ALL {
+            for to in Scale::ALL {
+                for value in [-40.0, 0.0, 36.6, 1000.0] {
+                    let Ok(start) = Temperature::new(value, from) else {
+                        continue;
+                    };
+                    let back = start.to(to).to(from);
+                    assert_eq!(back.scale(), from);
+                    assert!(
+                        (back.value() - value).abs() < 1e-9,
+                        "{from} -> {to}: {value}"
+                    );
+                }
+            }
+            let same = Temperature::new(12.3, from).unwrap();
+            assert_eq!(same.to(from), same);
+        }
+    }
+
+    #[test]
+    fn nothing_is_colder_than_absolute_zero() {
+        for scale in Scale::ALL {
+            let zero = scale.absolute_zero();
+            assert!(Temperature::new(zero, scale).is_ok(), "{scale}");
+            assert_eq!(Temperature::new(zero, scale).unwrap().as_kelvin(), 0.0);
+            assert_eq!(
+                Temperature::new(zero - 0.001, scale),
+                Err(TemperatureError::BelowAbsoluteZero {
+                    value: zero - 0.001,
+                    scale
+                })
+            );
+        }
+        // The slack only absorbs rounding
+        assert!(Temperature::kelvin(-1e-10).is_ok());
+        assert!(Temperature::kelvin(-1e-8).is_err());
+        assert_eq!(
+            Temperature::celsius(f64::NAN),
+            Err(TemperatureError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn temperatures_parse_with_any_unit_spelling() {
+        assert_eq!("98.6F".parse(), Temperature::fahrenheit(98.6));
+        assert_eq!("-40 °C".parse(), Temperature::celsius(-40.0));
+        assert_eq!("300 kelvin".parse(), Temperature::kelvin(300.0));
+        assert_eq!("20 Ré".parse(), Temperature::new(20.0, Scale::Reaumur));
+
+        assert_eq!(
+            "warm".parse::<Temperature>(),
+            Err(TemperatureError::InvalidNumber(String::from("warm")))
+        );
+        assert_eq!(
+            "20 X".parse::<Temperature>(),
+            Err(TemperatureError::UnknownScale(String::from("X")))
+        );
+        assert!("-300C".parse::<Temperature>().is_err());
+    }
+}
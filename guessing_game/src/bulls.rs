@@ -0,0 +1,303 @@
+/*
+ * Bulls and Cows (a digit Mastermind).
+ * The secret is a code of digits, a bull is a right digit in the right place
+ * and a cow is a right digit in the wrong place.
+ */
+
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+/// Longest code we allow, 10^5 codes is still fine for the solver to enumerate.
+pub const MAX_LENGTH: usize = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    BadLength(usize),
+    NotADigit(char),
+    WrongLength { expected: usize, got: usize },
+    Repeated(u8),
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::BadLength(length) => {
+                write!(
+                    f,
+                    "Code length {length} is not supported, use 1 to {MAX_LENGTH}"
+                )
+            }
+            CodeError::NotADigit(c) => write!(f, "`{c}` is not a digit"),
+            CodeError::WrongLength { expected, got } => {
+                write!(f, "The code has {expected} digits, you typed {got}")
+            }
+            CodeError::Repeated(digit) => {
+                write!(f, "The digit {digit} is repeated, repeats are off")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Code(Vec<u8>);
+
+impl Code {
+    pub fn digits(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for digit in &self.0 {
+            write!(f, "{digit}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    pub bulls: usize,
+    pub cows: usize,
+}
+
+/// Scores `guess` against `secret`, repeated digits are only counted as often as they appear in both.
+pub fn score(secret: &Code, guess: &Code) -> Score {
+    let mut bulls = 0;
+    let mut secret_counts = [0usize; 10];
+    let mut guess_counts = [0usize; 10];
+
+    for (&s, &g) in secret.0.iter().zip(&guess.0) {
+        if s == g {
+            bulls += 1;
+        } else {
+            secret_counts[s as usize] += 1;
+            guess_counts[g as usize] += 1;
+        }
+    }
+
+    let cows = secret_counts
+        .iter()
+        .zip(&guess_counts)
+        .map(|(&s, &g)| s.min(g))
+        .sum();
+
+    Score { bulls, cows }
+}
+
+/// Code length and whether a digit may appear more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodeConfig {
+    length: usize,
+    repeats: bool,
+}
+
+impl CodeConfig {
+    pub fn new(length: usize, repeats: bool) -> Result<Self, CodeError> {
+        if length == 0 || length > MAX_LENGTH {
+            return Err(CodeError::BadLength(length));
+        }
+
+        Ok(Self { length, repeats })
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn repeats(&self) -> bool {
+        self.repeats
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Code, CodeError> {
+        let mut digits = Vec::with_capacity(self.length);
+        for c in input.trim().chars() {
+            let digit = c.to_digit(10).ok_or(CodeError::NotADigit(c))? as u8;
+            if !self.repeats && digits.contains(&digit) {
+                return Err(CodeError::Repeated(digit));
+            }
+            digits.push(digit);
+        }
+
+        if digits.len() != self.length {
+            return Err(CodeError::WrongLength {
+                expected: self.length,
+                got: digits.len(),
+            });
+        }
+
+        Ok(Code(digits))
+    }
+
+    pub fn random(&self, rng: &mut impl Rng) -> Code {
+        if self.repeats {
+            Code((0..self.length).map(|_| rng.gen_range(0..10)).collect())
+        } else {
+            let mut digits: Vec<u8> = (0..10).collect();
+            digits.shuffle(rng);
+            digits.truncate(self.length);
+            Code(digits)
+        }
+    }
+
+    /// Every possible code, in increasing order.
+    pub fn all_codes(&self) -> Vec<Code> {
+        let total = 10usize.pow(self.length as u32);
+        (0..total)
+            .map(|mut n| {
+                let mut digits = vec![0; self.length];
+                for digit in digits.iter_mut().rev() {
+                    *digit = (n % 10) as u8;
+                    n /= 10;
+                }
+                Code(digits)
+            })
+            .filter(|code| self.repeats || is_distinct(code))
+            .collect()
+    }
+}
+
+fn is_distinct(code: &Code) -> bool {
+    let mut seen = [false; 10];
+    code.0
+        .iter()
+        .all(|&digit| !std::mem::replace(&mut seen[digit as usize], true))
+}
+
+/// One round of Bulls and Cows.
+#[derive(Debug)]
+pub struct BullsAndCows {
+    config: CodeConfig,
+    secret: Code,
+    attempts: u32,
+}
+
+impl BullsAndCows {
+    pub fn new(config: CodeConfig) -> Self {
+        Self::with_rng(config, ChaCha8Rng::from_entropy())
+    }
+
+    pub fn with_seed(config: CodeConfig, seed: u64) -> Self {
+        Self::with_rng(config, ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    fn with_rng(config: CodeConfig, mut rng: ChaCha8Rng) -> Self {
+        Self {
+            secret: config.random(&mut rng),
+            config,
+            attempts: 0,
+        }
+    }
+
+    pub fn config(&self) -> &CodeConfig {
+        &self.config
+    }
+
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn guess(&mut self, guess: &Code) -> Score {
+        self.attempts += 1;
+        score(&self.secret, guess)
+    }
+
+    pub fn is_won(&self, score: Score) -> bool {
+        score.bulls == self.config.length
+    }
+}
+
+/// Knuth style minimax solver: every guess minimises the largest group of codes
+/// that could still be the secret afterwards.
+#[derive(Debug)]
+pub struct Solver {
+    config: CodeConfig,
+    all: Vec<Code>,
+    candidates: Vec<Code>,
+    guesses: u32,
+}
+
+impl Solver {
+    /// Above this many score computations per guess we only try the remaining candidates.
+    const FULL_SEARCH_LIMIT: usize = 5_000_000;
+    /// Above this many candidates minimax gets too slow, any consistent code is played instead.
+    const MINIMAX_LIMIT: usize = 2_000;
+
+    pub fn new(config: CodeConfig) -> Self {
+        let all = config.all_codes();
+        Self {
+            config,
+            candidates: all.clone(),
+            all,
+            guesses: 0,
+        }
+    }
+
+    /// Codes that are still consistent with every score seen so far.
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    /// `None` when the scores contradict each other and no code is left.
+    pub fn next_guess(&self) -> Option<Code> {
+        if self.guesses == 0 {
+            return Some(self.opening());
+        }
+        if self.candidates.len() <= 2 || self.candidates.len() > Self::MINIMAX_LIMIT {
+            return self.candidates.first().cloned();
+        }
+
+        let pool = if self.all.len() * self.candidates.len() <= Self::FULL_SEARCH_LIMIT {
+            &self.all
+        } else {
+            &self.candidates
+        };
+
+        // Ties go to a code that could itself be the secret, then to the lowest code
+        pool.iter()
+            .map(|guess| {
+                let worst = self.worst_group(guess);
+                let impossible = !self.candidates.contains(guess);
+                ((worst, impossible), guess)
+            })
+            .min_by_key(|(key, _)| *key)
+            .map(|(_, guess)| guess.clone())
+    }
+
+    pub fn feedback(&mut self, guess: &Code, result: Score) {
+        self.guesses += 1;
+        self.candidates.retain(|code| score(code, guess) == result);
+    }
+
+    /// Knuth's 1122 opening generalised, half of the code one digit and half another.
+    fn opening(&self) -> Code {
+        let digits = (0..self.config.length)
+            .map(|i| {
+                if self.config.repeats {
+                    (i * 2 / self.config.length) as u8
+                } else {
+                    i as u8
+                }
+            })
+            .collect();
+        Code(digits)
+    }
+
+    fn worst_group(&self, guess: &Code) -> usize {
+        let side = self.config.length + 1;
+        let mut groups = vec![0usize; side * side];
+        for code in &self.candidates {
+            let Score { bulls, cows } = score(code, guess);
+            groups[bulls * side + cows] += 1;
+        }
+        groups.into_iter().max().unwrap_or(0)
+    }
+}
@@ -0,0 +1,207 @@
+/*
+ * Coach mode, explains every guess in bits. A guess splits the numbers that are still
+ * possible into smaller, equal and bigger, and the expected information of the answer is
+ * the entropy of that split. The midpoint gives the most, that is why binary search wins.
+ * Ratings go by the expected bits, the bits actually gained also depend on luck.
+ */
+
+use crate::{config::Config, strategy::Interval};
+use std::{cmp::Ordering, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuessReport {
+    pub guess: i128,
+    /// What was still possible before the guess.
+    pub before: Interval,
+    pub after: Interval,
+    /// What the answer actually told, log2 of how much smaller the interval got.
+    pub gained: f64,
+    /// What this guess could be expected to tell.
+    pub expected: f64,
+    /// What the midpoint could be expected to tell.
+    pub best: f64,
+}
+
+impl GuessReport {
+    /// The midpoint, the guess the coach would have made.
+    pub fn best_guess(&self) -> i128 {
+        self.before.midpoint()
+    }
+
+    pub fn rating(&self) -> Rating {
+        if !self.before.contains(self.guess) {
+            return Rating::Wasted;
+        }
+        // With one number left nothing can be learned, naming it is all there is to do
+        if self.best <= 0.0 {
+            return Rating::Excellent;
+        }
+        match self.expected / self.best {
+            share if share >= 0.95 => Rating::Excellent,
+            share if share >= 0.8 => Rating::Good,
+            share if share >= 0.5 => Rating::Fair,
+            _ => Rating::Poor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rating {
+    Excellent,
+    Good,
+    Fair,
+    Poor,
+    /// Outside the interval, the answer was known before asking.
+    Wasted,
+}
+
+impl fmt::Display for Rating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Rating::Excellent => "excellent",
+            Rating::Good => "good",
+            Rating::Fair => "fair",
+            Rating::Poor => "poor",
+            Rating::Wasted => "wasted",
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Coach {
+    interval: Interval,
+    reports: Vec<GuessReport>,
+}
+
+impl Coach {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            interval: Interval::from_config(config),
+            reports: Vec::new(),
+        }
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    pub fn reports(&self) -> &[GuessReport] {
+        &self.reports
+    }
+
+    /// `ordering` is `guess.cmp(&secret)`, as the game loop computes it.
+    pub fn observe(&mut self, guess: i128, ordering: Ordering) -> GuessReport {
+        let before = self.interval;
+        self.interval.narrow(guess, ordering);
+
+        // Hitting the number leaves nothing to learn, as if one number was left
+        let left = if ordering == Ordering::Equal {
+            1
+        } else {
+            self.interval.len().max(1)
+        };
+        let report = GuessReport {
+            guess,
+            before,
+            after: self.interval,
+            gained: (before.len() as f64 / left as f64).log2().max(0.0),
+            expected: expected_bits(before, guess),
+            best: expected_bits(before, before.midpoint()),
+        };
+
+        self.reports.push(report);
+        report
+    }
+}
+
+/// Entropy of the answer to `guess` when every number in `interval` is equally likely.
+pub fn expected_bits(interval: Interval, guess: i128) -> f64 {
+    if !interval.contains(guess) {
+        return 0.0;
+    }
+
+    let total = interval.len() as f64;
+    let below = guess.abs_diff(interval.low) as f64;
+    let above = interval.high.abs_diff(guess) as f64;
+
+    [below, 1.0, above]
+        .into_iter()
+        .filter(|&count| count > 0.0)
+        .map(|count| {
+            let p = count / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+pub struct CoachReport<'a>(pub &'a [GuessReport]);
+
+impl fmt::Display for CoachReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3}  {:>12}  {:>12}  {:>8}  {:>8}  {:>12}  Rating",
+            "#", "Guess", "Left before", "Expected", "Best", "Best guess"
+        )?;
+        for (index, report) in self.0.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>3}  {:>12}  {:>12}  {:>8.2}  {:>8.2}  {:>12}  {}",
+                index + 1,
+                report.guess,
+                report.before.len(),
+                report.expected,
+                report.best,
+                report.best_guess(),
+                report.rating()
+            )?;
+        }
+
+        let expected: f64 = self.0.iter().map(|report| report.expected).sum();
+        let best: f64 = self.0.iter().map(|report| report.best).sum();
+        if best > 0.0 {
+            writeln!(
+                f,
+                "Your guesses got {:.0}% of the information the midpoint would have.",
+                100.0 * expected / best
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Difficulty;
+
+    fn coach(min: i128, max: i128) -> Coach {
+        Coach::new(&Config::new(Difficulty::Custom, min, max, None).unwrap())
+    }
+
+    #[test]
+    fn naming_the_last_number_left_is_excellent() {
+        let report = coach(7, 7).observe(7, Ordering::Equal);
+        assert_eq!((report.expected, report.best), (0.0, 0.0));
+        assert_eq!(report.rating(), Rating::Excellent);
+
+        let mut narrowed = coach(1, 3);
+        narrowed.observe(2, Ordering::Less);
+        narrowed.observe(4, Ordering::Greater);
+        assert_eq!(narrowed.reports()[1].rating(), Rating::Wasted);
+        assert_eq!(
+            narrowed.observe(3, Ordering::Equal).rating(),
+            Rating::Excellent
+        );
+    }
+
+    #[test]
+    fn the_midpoint_is_excellent_and_the_edge_is_not() {
+        let mut coach = coach(1, 100);
+        assert_eq!(
+            coach.clone().observe(50, Ordering::Less).rating(),
+            Rating::Excellent
+        );
+        assert_eq!(coach.observe(1, Ordering::Less).rating(), Rating::Poor);
+    }
+}
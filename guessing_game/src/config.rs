@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+/// Difficulty presets, `Custom` is whatever range the player asked for with `--min/--max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Custom,
+}
+
+impl Difficulty {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom => "custom",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            "custom" => Ok(Difficulty::Custom),
+            _ => Err(ConfigError::UnknownDifficulty(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    UnknownDifficulty(String),
+    EmptyRange { min: i128, max: i128 },
+    ZeroAttempts,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::UnknownDifficulty(name) => write!(
+                f,
+                "Unknown difficulty `{name}`, expected easy, medium, hard or custom"
+            ),
+            ConfigError::EmptyRange { min, max } => {
+                write!(f, "The range {min}..={max} is empty, min must be <= max")
+            }
+            ConfigError::ZeroAttempts => write!(f, "The attempt limit must be at least 1"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Range and attempt limit for a round.
+///
+/// The bounds are `i128` so one type covers negative ranges as well as anything up to `u64::MAX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    difficulty: Difficulty,
+    min: i128,
+    max: i128,
+    max_attempts: Option<u32>,
+}
+
+impl Config {
+    pub fn preset(difficulty: Difficulty) -> Self {
+        let (min, max, max_attempts) = match difficulty {
+            Difficulty::Easy => (1, 10, None),
+            // Same as the original game
+            Difficulty::Medium | Difficulty::Custom => (1, 100, None),
+            Difficulty::Hard => (1, 1000, Some(10)),
+        };
+
+        Self {
+            difficulty,
+            min,
+            max,
+            max_attempts,
+        }
+    }
+
+    pub fn custom(min: i128, max: i128) -> Result<Self, ConfigError> {
+        Self::new(Difficulty::Custom, min, max, None)
+    }
+
+    /// Any combination, used to restore a config that was saved earlier.
+    pub fn new(
+        difficulty: Difficulty,
+        min: i128,
+        max: i128,
+        max_attempts: Option<u32>,
+    ) -> Result<Self, ConfigError> {
+        if min > max {
+            return Err(ConfigError::EmptyRange { min, max });
+        }
+
+        Self {
+            difficulty,
+            min,
+            max,
+            max_attempts: None,
+        }
+        .with_max_attempts(max_attempts)
+    }
+
+    /// Combines optional settings the way the command line does: a preset (medium by default),
+    /// `min`/`max` on their own switch to a custom range and the missing bound comes from the preset.
+    pub fn resolve(
+        difficulty: Option<Difficulty>,
+        min: Option<i128>,
+        max: Option<i128>,
+        max_attempts: Option<u32>,
+    ) -> Result<Self, ConfigError> {
+        let difficulty = difficulty.unwrap_or(Difficulty::Medium);
+        let preset = Self::preset(difficulty);
+
+        let config = if difficulty == Difficulty::Custom || min.is_some() || max.is_some() {
+            Self::custom(min.unwrap_or(preset.min), max.unwrap_or(preset.max))?
+        } else {
+            preset
+        };
+
+        match max_attempts {
+            Some(max_attempts) => config.with_max_attempts(Some(max_attempts)),
+            None => Ok(config),
+        }
+    }
+
+    /// `None` means unlimited attempts.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Result<Self, ConfigError> {
+        if max_attempts == Some(0) {
+            return Err(ConfigError::ZeroAttempts);
+        }
+
+        self.max_attempts = max_attempts;
+        Ok(self)
+    }
+
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    pub fn min(&self) -> i128 {
+        self.min
+    }
+
+    pub fn max(&self) -> i128 {
+        self.max
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    /// How many numbers the secret can be, saturating at `u128::MAX` for the full `i128` range.
+    pub fn range_size(&self) -> u128 {
+        self.max.abs_diff(self.min).saturating_add(1)
+    }
+
+    pub fn contains(&self, number: i128) -> bool {
+        (self.min..=self.max).contains(&number)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::preset(Difficulty::Medium)
+    }
+}
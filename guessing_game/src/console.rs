@@ -0,0 +1,92 @@
+/*
+ * Line input for the interactive modes.
+ * Stdin is read on its own thread and Ctrl-C is turned into a message on the same channel,
+ * so a prompt can tell a typed line, the end of the input and an interrupt apart.
+ */
+
+use std::{
+    cell::Cell,
+    io,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Line(String),
+    /// Stdin was closed (or could not be read), nothing more will come.
+    Eof,
+    /// The player pressed Ctrl-C.
+    Interrupt,
+}
+
+pub struct Console {
+    receiver: Receiver<Input>,
+    closed: Cell<bool>,
+}
+
+impl Console {
+    /// Starts reading stdin and catching Ctrl-C, only one console may exist per process.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        let interrupts = sender.clone();
+        // If the handler cannot be installed Ctrl-C simply ends the process as usual
+        let _ = ctrlc::set_handler(move || {
+            let _ = interrupts.send(Input::Interrupt);
+        });
+
+        thread::spawn(move || loop {
+            let mut line = String::new(); // mutable value, defaults are immutables
+
+            let input = match io::stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => Input::Eof,
+                Ok(_) => Input::Line(line),
+            };
+            let done = input == Input::Eof;
+
+            if sender.send(input).is_err() || done {
+                return;
+            }
+        });
+
+        Self {
+            receiver,
+            closed: Cell::new(false),
+        }
+    }
+
+    /// Blocks until the next line, end of input or Ctrl-C.
+    pub fn read(&self) -> Input {
+        // Once stdin is gone every later read is the end of the input too
+        if self.closed.get() {
+            return Input::Eof;
+        }
+
+        let input = self.receiver.recv().unwrap_or(Input::Eof);
+        self.closed.set(input == Input::Eof);
+        input
+    }
+
+    /// Like `read`, but gives up after `timeout` and returns `None`.
+    pub fn read_timeout(&self, timeout: Duration) -> Option<Input> {
+        if self.closed.get() {
+            return Some(Input::Eof);
+        }
+
+        let input = match self.receiver.recv_timeout(timeout) {
+            Ok(input) => input,
+            Err(RecvTimeoutError::Timeout) => return None,
+            Err(RecvTimeoutError::Disconnected) => Input::Eof,
+        };
+        self.closed.set(input == Input::Eof);
+        Some(input)
+    }
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Self::new()
+    }
+}
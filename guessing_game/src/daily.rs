@@ -0,0 +1,328 @@
+/*
+ * Daily challenge. The range and secret only depend on the date, UTC so the whole team
+ * shares the same day, and the result can be shared as a line of arrows.
+ * Each profile gets one scored attempt per day, kept in `daily.tsv` next to the leaderboard.
+ * The attempt is claimed with the first guess, so quitting and starting over does not help.
+ */
+
+use crate::{
+    config::{Config, Difficulty},
+    game::Game,
+    leaderboard, strategy,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    cmp::Ordering,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Range sizes the days rotate through.
+const SIZES: [i128; 4] = [100, 200, 500, 1000];
+
+/// A day in the proleptic Gregorian calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    /// Today in UTC.
+    pub fn today() -> Self {
+        let seconds = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_secs() as i64,
+            Err(before) => -(before.duration().as_secs() as i64),
+        };
+        Self::from_days(seconds.div_euclid(86_400))
+    }
+
+    /// The date `days` days after 1970-01-01.
+    pub fn from_days(days: i64) -> Self {
+        // Howard Hinnant's civil_from_days, eras are 400 year cycles starting in March
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Self { year, month, day }
+    }
+
+    /// Days since 1970-01-01, the inverse of `from_days`.
+    pub fn days(&self) -> i64 {
+        let year = self.year - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5
+            + i64::from(self.day)
+            - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// `YYYY-MM-DD`, days that do not exist like the 31st of April and years past 9999 are refused.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("`{s}` is not a date, expected YYYY-MM-DD");
+        let mut parts = s.trim().splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let date = Self {
+            year: year.parse().map_err(|_| invalid())?,
+            month: month.parse().map_err(|_| invalid())?,
+            day: day.parse().map_err(|_| invalid())?,
+        };
+        // Out of range days roll over into the next month, so they do not survive the round trip.
+        // Four digit years keep `days` far from overflowing.
+        if !(0..=9999).contains(&date.year)
+            || date.month == 0
+            || date.month > 12
+            || date.day == 0
+            || Self::from_days(date.days()) != date
+        {
+            return Err(invalid());
+        }
+        Ok(date)
+    }
+}
+
+/// The puzzle of one day.
+#[derive(Debug, Clone, Copy)]
+pub struct Puzzle {
+    pub date: Date,
+    pub config: Config,
+    pub secret: i128,
+}
+
+impl Puzzle {
+    pub fn for_date(date: Date) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(0xDA11_0000_0000_0000 ^ date.days() as u64);
+
+        let size = SIZES[rng.gen_range(0..SIZES.len())];
+        let min = 1 + 10 * rng.gen_range(0..10);
+        let max = min + size - 1;
+        // A little slack over what binary search needs, like Wordle gives six rows
+        let max_attempts = strategy::optimal_worst_case(size as u128) + 2;
+        let config = Config::new(Difficulty::Custom, min, max, Some(max_attempts))
+            .expect("the daily ranges are valid");
+
+        Self {
+            date,
+            config,
+            secret: rng.gen_range(min..=max),
+        }
+    }
+
+    pub fn game(&self) -> Game {
+        Game::with_secret(self.config, self.secret)
+    }
+}
+
+/// The pasteable result, a header and one arrow per guess pointing to the secret.
+pub fn share_text(date: Date, game: &Game) -> String {
+    let mut won = false;
+    let arrows: String = game
+        .history()
+        .map(|(_, ordering)| match ordering {
+            Ordering::Less => "⬆️",
+            Ordering::Greater => "⬇️",
+            Ordering::Equal => {
+                won = true;
+                "✅"
+            }
+        })
+        .collect();
+
+    let attempts = if won {
+        game.attempts().to_string()
+    } else {
+        String::from("X")
+    };
+    let limit = game
+        .config()
+        .max_attempts()
+        .map_or_else(String::new, |limit| format!("/{limit}"));
+
+    format!("Guessing Game {date} {attempts}{limit}\n{arrows}")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DailyResult {
+    /// Claimed but never finished, the program was killed half way.
+    Playing,
+    Won {
+        attempts: u32,
+    },
+    Lost,
+}
+
+impl fmt::Display for DailyResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Playing => f.write_str("unfinished"),
+            Self::Won { attempts } => write!(f, "won in {attempts}"),
+            Self::Lost => f.write_str("lost"),
+        }
+    }
+}
+
+impl DailyResult {
+    fn as_field(&self) -> String {
+        match self {
+            Self::Playing => String::from("playing"),
+            Self::Won { attempts } => attempts.to_string(),
+            Self::Lost => String::from("X"),
+        }
+    }
+
+    fn from_field(field: &str) -> Option<Self> {
+        match field {
+            "playing" => Some(Self::Playing),
+            "X" => Some(Self::Lost),
+            attempts => attempts.parse().ok().map(|attempts| Self::Won { attempts }),
+        }
+    }
+}
+
+/// Scored daily attempts, one `date, name, result` line per change, the last one counts.
+#[derive(Debug, Clone)]
+pub struct DailyLog {
+    path: PathBuf,
+}
+
+impl DailyLog {
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `$XDG_DATA_HOME/guessing_game/daily.tsv`
+    pub fn default_path() -> Option<PathBuf> {
+        Some(leaderboard::data_dir()?.join("daily.tsv"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The scored attempt of `name` on `date`, if there is one.
+    pub fn result(&self, date: Date, name: &str) -> io::Result<Option<DailyResult>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        file.lock_shared()?;
+        find(BufReader::new(&file), date, name)
+    }
+
+    /// Starts the scored attempt, `false` if `name` already had one on `date`.
+    pub fn claim(&self, date: Date, name: &str) -> io::Result<bool> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Checking and claiming under one lock, two terminals cannot both get the attempt
+        let mut file = OpenOptions::new()
+            .create(true)
+            .read(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock()?;
+        if find(BufReader::new(&file), date, name)?.is_some() {
+            return Ok(false);
+        }
+
+        file.write_all(line(date, name, DailyResult::Playing).as_bytes())?;
+        file.flush().map(|_| true)
+    }
+
+    pub fn finish(&self, date: Date, name: &str, result: DailyResult) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock()?;
+        file.write_all(line(date, name, result).as_bytes())?;
+        file.flush()
+    }
+}
+
+fn line(date: Date, name: &str, result: DailyResult) -> String {
+    format!(
+        "{date}\t{}\t{}\n",
+        leaderboard::clean_name(name),
+        result.as_field()
+    )
+}
+
+fn find(reader: impl BufRead, date: Date, name: &str) -> io::Result<Option<DailyResult>> {
+    let date = date.to_string();
+    let name = leaderboard::clean_name(name);
+    let mut found = None;
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut fields = line.split('\t');
+        if let (Some(day), Some(player), Some(result), None) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        {
+            if day == date && player == name {
+                found = DailyResult::from_field(result).or(found);
+            }
+        }
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dates_round_trip_and_huge_years_are_refused() {
+        for text in ["1970-01-01", "2000-02-29", "9999-12-31", "0000-03-01"] {
+            let date: Date = text.parse().unwrap();
+            assert_eq!(date.to_string(), text);
+            assert_eq!(Date::from_days(date.days()), date);
+        }
+        for text in [
+            "2023-02-29",
+            "2024-04-31",
+            "10000-01-01",
+            "9000000000000000000-01-01",
+        ] {
+            assert!(text.parse::<Date>().is_err(), "{text}");
+        }
+    }
+}
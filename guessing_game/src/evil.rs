@@ -0,0 +1,99 @@
+/*
+ * The evil host never picks a secret up front. It keeps every number that is still
+ * consistent with its answers, and answers each guess so the bigger part survives.
+ * It only commits when one number is left, so every guesser gets its worst case,
+ * which is why binary search (halving what is left) is the best anyone can do.
+ */
+
+use crate::{
+    config::Config,
+    game::{Host, Outcome},
+    strategy::Interval,
+};
+use std::cmp::Ordering;
+
+#[derive(Debug)]
+pub struct EvilHost {
+    config: Config,
+    interval: Interval,
+    attempts: u32,
+    over: bool,
+}
+
+impl EvilHost {
+    pub fn new(config: Config) -> Self {
+        Self {
+            interval: Interval::from_config(&config),
+            config,
+            attempts: 0,
+            over: false,
+        }
+    }
+
+    /// The numbers that are still consistent with every answer so far.
+    pub fn remaining(&self) -> Interval {
+        self.interval
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// `guess.cmp(&secret)` for the answer that keeps the most numbers alive.
+    fn answer(&self, guess: i128) -> Ordering {
+        if guess < self.interval.low {
+            return Ordering::Less;
+        }
+        if guess > self.interval.high {
+            return Ordering::Greater;
+        }
+
+        // Numbers left below and above the guess, ties go to the lower side
+        let below = guess.abs_diff(self.interval.low);
+        let above = self.interval.high.abs_diff(guess);
+        match (below, above) {
+            (0, 0) => Ordering::Equal,
+            (below, above) if below >= above => Ordering::Greater,
+            _ => Ordering::Less,
+        }
+    }
+}
+
+impl Host for EvilHost {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn check(&mut self, guess: i128) -> Outcome {
+        if self.over {
+            return Outcome::Invalid;
+        }
+
+        self.attempts += 1;
+        let ordering = self.answer(guess);
+        self.interval.narrow(guess, ordering);
+
+        let outcome = match ordering {
+            Ordering::Equal => Outcome::Won {
+                attempts: self.attempts,
+            },
+            // Out of attempts, now it has to name a number, any that is left will do
+            _ if self.config.max_attempts() == Some(self.attempts) => Outcome::Lost {
+                secret: self.interval.low,
+            },
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+        };
+
+        self.over = matches!(outcome, Outcome::Won { .. } | Outcome::Lost { .. });
+        outcome
+    }
+
+    fn next_round(&mut self) {
+        *self = Self::new(self.config);
+    }
+}
@@ -0,0 +1,61 @@
+/*
+ * Provably fair rounds. Before the first guess the host shows a commitment, the SHA-256
+ * of "<secret>:<salt>" in lowercase hex, and reveals secret and salt when the round ends.
+ * Anyone can redo the hash without this program:
+ *
+ *   printf '%s' '42:3f9a...' | sha256sum
+ *
+ * The salt is 16 random bytes in hex, without it the commitment for a small range
+ * could be broken by hashing every number in it.
+ */
+
+use sha2::{Digest, Sha256};
+
+/// A secret together with the salt that hides it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seal {
+    secret: i128,
+    salt: String,
+}
+
+impl Seal {
+    /// Seals `secret` with a fresh random salt.
+    pub fn new(secret: i128) -> Self {
+        let salt = rand::random::<[u8; 16]>();
+        Self::with_salt(secret, to_hex(&salt))
+    }
+
+    /// Seals `secret` with a salt picked earlier, for a resumed round.
+    pub fn with_salt(secret: i128, salt: impl Into<String>) -> Self {
+        Self {
+            secret,
+            salt: salt.into(),
+        }
+    }
+
+    pub fn secret(&self) -> i128 {
+        self.secret
+    }
+
+    pub fn salt(&self) -> &str {
+        &self.salt
+    }
+
+    pub fn commitment(&self) -> String {
+        commitment(self.secret, &self.salt)
+    }
+}
+
+/// SHA-256 of "<secret>:<salt>" in lowercase hex.
+pub fn commitment(secret: i128, salt: &str) -> String {
+    to_hex(&Sha256::digest(format!("{secret}:{salt}")))
+}
+
+/// Whether the revealed pair is the one that was committed to, the hex may be in any case.
+pub fn verify(commitment: &str, secret: i128, salt: &str) -> bool {
+    self::commitment(secret, salt).eq_ignore_ascii_case(commitment.trim())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
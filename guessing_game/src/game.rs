@@ -0,0 +1,212 @@
+use crate::{config::Config, parse::parse_guess};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+
+/// What happened after a single guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Won {
+        attempts: u32,
+    },
+    /// The attempt limit ran out, the secret is revealed.
+    Lost {
+        secret: i128,
+    },
+    /// The input was not a number (or the round is already over), it does not count as an attempt.
+    Invalid,
+}
+
+/// One round of the game, the secret is picked once and kept until the round is over.
+///
+/// The game owns its RNG, so a game built with `with_seed` always draws the same
+/// sequence of secrets for every round (ChaCha8 gives the same stream on every platform).
+#[derive(Debug)]
+pub struct Game {
+    rng: ChaCha8Rng,
+    config: Config,
+    secret_number: i128,
+    /// Every counted guess of this round, in order.
+    guesses: Vec<i128>,
+    over: bool,
+}
+
+impl Game {
+    pub fn new(config: Config) -> Self {
+        Self::with_rng(config, ChaCha8Rng::from_entropy())
+    }
+
+    pub fn with_seed(config: Config, seed: u64) -> Self {
+        Self::with_rng(config, ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    pub fn with_secret(config: Config, secret_number: i128) -> Self {
+        Self {
+            rng: ChaCha8Rng::from_entropy(),
+            config,
+            secret_number,
+            guesses: Vec::new(),
+            over: false,
+        }
+    }
+
+    fn with_rng(config: Config, mut rng: ChaCha8Rng) -> Self {
+        let secret_number = rng.gen_range(config.min()..=config.max());
+        Self {
+            rng,
+            config,
+            secret_number,
+            guesses: Vec::new(),
+            over: false,
+        }
+    }
+
+    /// Rebuilds a saved round by replaying its guesses, so attempts and the over flag come out the same.
+    pub fn resume(config: Config, secret_number: i128, guesses: &[i128]) -> Self {
+        let mut game = Self::with_secret(config, secret_number);
+        for &guess in guesses {
+            game.check(guess);
+        }
+        game
+    }
+
+    /// Starts the next round, the new secret comes from the same RNG.
+    pub fn next_round(&mut self) {
+        self.secret_number = self.rng.gen_range(self.config.min()..=self.config.max());
+        self.guesses.clear();
+        self.over = false;
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn secret(&self) -> i128 {
+        self.secret_number
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32
+    }
+
+    pub fn guesses(&self) -> &[i128] {
+        &self.guesses
+    }
+
+    /// Every guess with the feedback it got.
+    pub fn history(&self) -> impl Iterator<Item = (i128, Ordering)> + '_ {
+        self.guesses
+            .iter()
+            .map(|&guess| (guess, self.compare(guess)))
+    }
+
+    /// `None` when the round has no attempt limit.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.config
+            .max_attempts()
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// Parses the raw line typed by the player and checks it against the secret.
+    /// Use `parse::parse_guess` directly to tell the player why a line was invalid.
+    pub fn guess(&mut self, input: &str) -> Outcome {
+        match parse_guess(input) {
+            Ok(num) => self.check(num),
+            Err(_) => Outcome::Invalid,
+        }
+    }
+
+    pub fn check(&mut self, guess: i128) -> Outcome {
+        if self.over {
+            return Outcome::Invalid;
+        }
+
+        self.guesses.push(guess);
+        let outcome = match self.compare(guess) {
+            Ordering::Equal => Outcome::Won {
+                attempts: self.attempts(),
+            },
+            _ if self.attempts_left() == Some(0) => Outcome::Lost {
+                secret: self.secret_number,
+            },
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+        };
+
+        self.over = matches!(outcome, Outcome::Won { .. } | Outcome::Lost { .. });
+        outcome
+    }
+
+    /// The raw `Ordering` feedback, without counting an attempt.
+    pub fn compare(&self, guess: i128) -> Ordering {
+        guess.cmp(&self.secret_number)
+    }
+}
+
+/// Anything that answers guesses the way `Game` does, so the bots can play other hosts too.
+pub trait Host {
+    fn config(&self) -> &Config;
+
+    fn check(&mut self, guess: i128) -> Outcome;
+
+    fn next_round(&mut self);
+}
+
+impl Host for Game {
+    fn config(&self) -> &Config {
+        Game::config(self)
+    }
+
+    fn check(&mut self, guess: i128) -> Outcome {
+        Game::check(self, guess)
+    }
+
+    fn next_round(&mut self) {
+        Game::next_round(self)
+    }
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The secret of this round and the next `rounds - 1`, guessing `guess` once in each.
+    fn secrets(game: &mut Game, rounds: usize, guess: Option<i128>) -> Vec<i128> {
+        (0..rounds)
+            .map(|_| {
+                let secret = game.secret();
+                if let Some(guess) = guess {
+                    game.check(guess);
+                }
+                game.next_round();
+                secret
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_same_seed_draws_the_same_secrets_every_round() {
+        let config = Config::default();
+        let first = secrets(&mut Game::with_seed(config, 42), 50, None);
+
+        assert_eq!(secrets(&mut Game::with_seed(config, 42), 50, None), first);
+        // Guessing does not touch the RNG, so it cannot shift the sequence
+        assert_eq!(
+            secrets(&mut Game::with_seed(config, 42), 50, Some(50)),
+            first
+        );
+        assert_ne!(secrets(&mut Game::with_seed(config, 43), 50, None), first);
+    }
+}
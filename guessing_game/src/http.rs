@@ -0,0 +1,598 @@
+/*
+ * Small HTTP/JSON API so browsers and remote clients can play.
+ *
+ *   POST /games                 {"difficulty":"hard"} or {"min":1,"max":500,"max_attempts":9,"seed":7}
+ *                               -> 201 {"id":"...","difficulty":"custom","min":1,"max":500,"max_attempts":9}
+ *   POST /games/{id}/guesses    {"guess":42}
+ *                               -> 200 {"result":"too_small","guess":42,"attempt":1}
+ *   GET  /games/{id}            -> 200 the round with its history
+ *
+ * Errors come back as {"error":"..."} with a 4xx status. Rounds nobody touched for the
+ * session timeout are dropped, after that their id answers 404. New rounds get 503 while
+ * too many are in progress.
+ * Only what this API needs of HTTP/1.1 is implemented, every response closes the connection.
+ */
+
+use crate::{
+    config::{Config, Difficulty},
+    game::{Game, Outcome},
+    protocol::GuessResult,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Bodies bigger than this are refused, a guess is a few bytes.
+const MAX_BODY: usize = 64 * 1024;
+
+/// The request line and every header must fit, so a client cannot grow one line forever.
+const MAX_LINE: usize = 8 * 1024;
+
+const MAX_HEADERS: usize = 100;
+
+/// Rounds in progress before new ones are refused, each one is a few hundred bytes.
+pub const DEFAULT_MAX_SESSIONS: usize = 10_000;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CreateGame {
+    difficulty: Option<Difficulty>,
+    min: Option<i128>,
+    max: Option<i128>,
+    max_attempts: Option<u32>,
+    seed: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SubmitGuess {
+    guess: i128,
+}
+
+#[derive(Debug, Serialize)]
+struct HistoryEntry {
+    guess: i128,
+    result: GuessResult,
+}
+
+struct Session {
+    game: Game,
+    last_active: Instant,
+}
+
+type Sessions = Arc<Mutex<HashMap<String, Session>>>;
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    body: serde_json::Value,
+}
+
+impl Response {
+    fn ok(status: u16, body: serde_json::Value) -> Self {
+        Self { status, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+pub struct ApiServer {
+    listener: TcpListener,
+    sessions: Sessions,
+    timeout: Duration,
+    max_sessions: usize,
+}
+
+impl ApiServer {
+    /// `timeout` is how long a round may sit idle before it is dropped.
+    pub fn bind(addr: impl ToSocketAddrs, timeout: Duration) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            sessions: Arc::default(),
+            timeout,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+        })
+    }
+
+    /// How many rounds may be in progress at once.
+    pub fn with_max_sessions(mut self, max_sessions: usize) -> Self {
+        self.max_sessions = max_sessions;
+        self
+    }
+
+    /// Useful after binding to port 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Serves forever, one thread per connection.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let sessions = Arc::clone(&self.sessions);
+            let (timeout, max_sessions) = (self.timeout, self.max_sessions);
+
+            thread::spawn(move || {
+                // A client that hangs up half way is no concern of the other players
+                let _ = handle_connection(stream, &sessions, timeout, max_sessions);
+            });
+        }
+        Ok(())
+    }
+
+    /// Serves on a background thread, handy for tests and for embedding.
+    pub fn spawn(self) -> io::Result<SocketAddr> {
+        let addr = self.local_addr()?;
+        thread::spawn(move || self.serve());
+        Ok(addr)
+    }
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    sessions: &Sessions,
+    timeout: Duration,
+    max_sessions: usize,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let response = match read_request(&mut reader)? {
+        Ok(request) => route(sessions, timeout, max_sessions, &request),
+        Err(response) => response,
+    };
+
+    write_response(stream, &response)
+}
+
+/// The outer error is the connection failing, the inner one a request we answer with a 4xx.
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let Some(request_line) = read_line(reader)? else {
+        return Ok(Err(Response::error(431, "request line too long")));
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Ok(Err(Response::error(400, "malformed request line")));
+    };
+
+    let mut content_length = 0;
+    for count in 0.. {
+        let Some(header) = read_line(reader)? else {
+            return Ok(Err(Response::error(431, "header line too long")));
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return Ok(Err(Response::error(431, "too many headers")));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                match value.trim().parse() {
+                    Ok(length) => content_length = length,
+                    Err(_) => return Ok(Err(Response::error(400, "bad Content-Length"))),
+                }
+            }
+        }
+    }
+
+    if content_length > MAX_BODY {
+        return Ok(Err(Response::error(413, "request body too large")));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    }))
+}
+
+/// One line of at most `MAX_LINE` bytes, `None` if it is longer. Empty at the end of the input.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.by_ref().take(MAX_LINE as u64).read_line(&mut line)?;
+
+    if line.len() == MAX_LINE && !line.ends_with('\n') {
+        return Ok(None);
+    }
+    Ok(Some(line))
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> io::Result<()> {
+    let body = response.body.to_string();
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn route(
+    sessions: &Sessions,
+    timeout: Duration,
+    max_sessions: usize,
+    request: &Request,
+) -> Response {
+    let Request { method, path, body } = request;
+    let mut sessions = sessions
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    sessions.retain(|_, session| session.last_active.elapsed() < timeout);
+
+    let segments: Vec<&str> = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match (method.as_str(), segments.as_slice()) {
+        ("POST", ["games"]) => create_game(&mut sessions, max_sessions, body),
+        ("GET", ["games", id]) => match sessions.get_mut(*id) {
+            Some(session) => {
+                session.last_active = Instant::now();
+                Response::ok(200, describe(id, &session.game))
+            }
+            None => Response::error(404, format!("no game with id {id}")),
+        },
+        ("POST", ["games", id, "guesses"]) => match sessions.get_mut(*id) {
+            Some(session) => {
+                session.last_active = Instant::now();
+                submit_guess(&mut session.game, body)
+            }
+            None => Response::error(404, format!("no game with id {id}")),
+        },
+        (_, ["games"] | ["games", _] | ["games", _, "guesses"]) => {
+            Response::error(405, format!("{method} is not allowed on {path}"))
+        }
+        _ => Response::error(404, format!("nothing at {path}")),
+    }
+}
+
+fn create_game(
+    sessions: &mut HashMap<String, Session>,
+    max_sessions: usize,
+    body: &[u8],
+) -> Response {
+    if sessions.len() >= max_sessions {
+        return Response::error(503, "too many games in progress, try again later");
+    }
+
+    // An empty body is a medium round
+    let request: CreateGame = if body.iter().all(u8::is_ascii_whitespace) {
+        CreateGame::default()
+    } else {
+        match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return Response::error(400, e.to_string()),
+        }
+    };
+
+    for (name, bound) in [("min", request.min), ("max", request.max)] {
+        if let Some(bound) = bound.filter(|&bound| !fits_json(bound)) {
+            return Response::error(
+                400,
+                format!("{name} {bound} is too large for a JSON number"),
+            );
+        }
+    }
+    let config = match Config::resolve(
+        request.difficulty,
+        request.min,
+        request.max,
+        request.max_attempts,
+    ) {
+        Ok(config) => config,
+        Err(e) => return Response::error(400, e.to_string()),
+    };
+    let game = match request.seed {
+        Some(seed) => Game::with_seed(config, seed),
+        None => Game::new(config),
+    };
+
+    let id = loop {
+        let id = format!("{:016x}", rand::random::<u64>());
+        if !sessions.contains_key(&id) {
+            break id;
+        }
+    };
+
+    let response = Response::ok(
+        201,
+        json!({
+            "id": id,
+            "difficulty": config.difficulty(),
+            "min": config.min(),
+            "max": config.max(),
+            "max_attempts": config.max_attempts(),
+        }),
+    );
+    sessions.insert(
+        id,
+        Session {
+            game,
+            last_active: Instant::now(),
+        },
+    );
+    response
+}
+
+fn submit_guess(game: &mut Game, body: &[u8]) -> Response {
+    let request: SubmitGuess = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => return Response::error(400, format!("expected {{\"guess\": <integer>}}: {e}")),
+    };
+    // Checked before the guess counts, the answer has to repeat it
+    if !fits_json(request.guess) {
+        return Response::error(
+            400,
+            format!("{} is too large for a JSON number", request.guess),
+        );
+    }
+
+    let outcome = game.check(request.guess);
+    let Some(result) = GuessResult::from_outcome(outcome) else {
+        return Response::error(409, "this round is already over");
+    };
+
+    let mut body = json!({
+        "result": result,
+        "guess": request.guess,
+        "attempt": game.attempts(),
+    });
+    if let Outcome::Lost { secret } = outcome {
+        body["secret"] = json!(secret);
+    }
+    Response::ok(200, body)
+}
+
+/// serde_json only writes integers that fit an i64 or a u64, `json!` panics on the rest.
+fn fits_json(number: i128) -> bool {
+    i64::try_from(number).is_ok() || u64::try_from(number).is_ok()
+}
+
+fn describe(id: &str, game: &Game) -> serde_json::Value {
+    let config = game.config();
+    let history: Vec<HistoryEntry> = game
+        .history()
+        .map(|(guess, ordering)| HistoryEntry {
+            guess,
+            result: ordering.into(),
+        })
+        .collect();
+
+    let mut body = json!({
+        "id": id,
+        "difficulty": config.difficulty(),
+        "min": config.min(),
+        "max": config.max(),
+        "max_attempts": config.max_attempts(),
+        "attempts": game.attempts(),
+        "over": game.is_over(),
+        "history": history,
+    });
+    // The secret is only given away once it can no longer help
+    if game.is_over() {
+        body["secret"] = json!(game.secret());
+    }
+    body
+}
+
+/// Minimal client for the API, returns the status code and the JSON body.
+pub fn request(
+    addr: impl ToSocketAddrs,
+    method: &str,
+    path: &str,
+    body: Option<&serde_json::Value>,
+) -> io::Result<(u16, serde_json::Value)> {
+    let mut stream = TcpStream::connect(addr)?;
+    let body = body.map(|body| body.to_string()).unwrap_or_default();
+    write!(
+        stream,
+        "{method} {path} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "malformed HTTP response");
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(invalid)?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(invalid)?;
+
+    Ok((status, serde_json::from_str(body)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn(timeout: Duration, max_sessions: usize) -> SocketAddr {
+        ApiServer::bind("127.0.0.1:0", timeout)
+            .unwrap()
+            .with_max_sessions(max_sessions)
+            .spawn()
+            .unwrap()
+    }
+
+    fn create(addr: SocketAddr, body: serde_json::Value) -> (u16, serde_json::Value) {
+        request(addr, "POST", "/games", Some(&body)).unwrap()
+    }
+
+    #[test]
+    fn a_round_is_created_guessed_and_described() {
+        let addr = spawn(Duration::from_secs(60), DEFAULT_MAX_SESSIONS);
+        let (status, game) = create(addr, json!({"min": 1, "max": 100, "max_attempts": 7}));
+        assert_eq!(status, 201);
+        assert_eq!(game["difficulty"], "custom");
+        assert_eq!(
+            (game["min"].as_i64(), game["max"].as_i64()),
+            (Some(1), Some(100))
+        );
+        let id = game["id"].as_str().unwrap();
+        let guesses = format!("/games/{id}/guesses");
+
+        // Binary search always finds one of 100 numbers in 7 guesses
+        let (mut low, mut high) = (1, 100);
+        let mut results = Vec::new();
+        loop {
+            let guess = (low + high) / 2;
+            let (status, body) =
+                request(addr, "POST", &guesses, Some(&json!({ "guess": guess }))).unwrap();
+            assert_eq!(status, 200);
+            assert_eq!(body["attempt"], results.len() + 1);
+            let result = body["result"].as_str().unwrap().to_string();
+            match result.as_str() {
+                "too_small" => low = guess + 1,
+                "too_big" => high = guess - 1,
+                "won" => {
+                    results.push((guess, result));
+                    break;
+                }
+                other => panic!("unexpected result {other}"),
+            }
+            results.push((guess, result));
+        }
+
+        let (status, body) = request(addr, "GET", &format!("/games/{id}"), None).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body["over"], true);
+        assert_eq!(body["attempts"], results.len());
+        assert_eq!(body["secret"], results.last().unwrap().0);
+        let history: Vec<(i64, String)> = body["history"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| {
+                let guess = entry["guess"].as_i64().unwrap();
+                (guess, entry["result"].as_str().unwrap().to_string())
+            })
+            .collect();
+        assert_eq!(history, results);
+
+        let (status, _) = request(addr, "POST", &guesses, Some(&json!({"guess": 1}))).unwrap();
+        assert_eq!(status, 409);
+        let (status, _) = request(addr, "POST", &guesses, Some(&json!({"guess": "x"}))).unwrap();
+        assert_eq!(status, 400);
+    }
+
+    /// Sends `body` as it is, `request` only takes numbers serde_json can hold.
+    fn raw(addr: SocketAddr, method: &str, path: &str, body: &str) -> u16 {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response.split_whitespace().nth(1).unwrap().parse().unwrap()
+    }
+
+    #[test]
+    fn numbers_beyond_json_are_refused_before_they_count() {
+        let addr = spawn(Duration::from_secs(60), DEFAULT_MAX_SESSIONS);
+        let too_big = i128::from(u64::MAX) + 1;
+        let body = format!("{{\"min\": 1, \"max\": {too_big}}}");
+        assert_eq!(raw(addr, "POST", "/games", &body), 400);
+
+        let (_, game) = create(addr, json!({"min": 1, "max": 100}));
+        let path = format!("/games/{}", game["id"].as_str().unwrap());
+        let too_small = i128::from(i64::MIN) - 1;
+        let body = format!("{{\"guess\": {too_small}}}");
+        assert_eq!(raw(addr, "POST", &format!("{path}/guesses"), &body), 400);
+
+        let (status, body) = request(addr, "GET", &path, None).unwrap();
+        assert_eq!(status, 200);
+        assert_eq!(body["attempts"], 0);
+    }
+
+    #[test]
+    fn idle_rounds_expire() {
+        let addr = spawn(Duration::from_millis(200), DEFAULT_MAX_SESSIONS);
+        let (_, game) = create(addr, json!({}));
+        let path = format!("/games/{}", game["id"].as_str().unwrap());
+
+        assert_eq!(request(addr, "GET", &path, None).unwrap().0, 200);
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(request(addr, "GET", &path, None).unwrap().0, 404);
+    }
+
+    #[test]
+    fn new_rounds_are_refused_while_too_many_are_in_progress() {
+        let addr = spawn(Duration::from_millis(200), 2);
+        assert_eq!(create(addr, json!({})).0, 201);
+        assert_eq!(create(addr, json!({})).0, 201);
+        assert_eq!(create(addr, json!({})).0, 503);
+
+        // Expired rounds make room again
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(create(addr, json!({})).0, 201);
+    }
+
+    #[test]
+    fn endless_header_lines_are_cut_off() {
+        let long = format!(
+            "GET /games HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+            "a".repeat(MAX_LINE)
+        );
+        let Err(response) = read_request(&mut long.as_bytes()).unwrap() else {
+            panic!("the long header should be refused");
+        };
+        assert_eq!(response.status, 431);
+
+        let many = format!(
+            "GET /games HTTP/1.1\r\n{}\r\n",
+            "X: y\r\n".repeat(MAX_HEADERS + 1)
+        );
+        let Err(response) = read_request(&mut many.as_bytes()).unwrap() else {
+            panic!("that many headers should be refused");
+        };
+        assert_eq!(response.status, 431);
+
+        let fine = format!(
+            "GET /games HTTP/1.1\r\n{}\r\n",
+            "X: y\r\n".repeat(MAX_HEADERS)
+        );
+        assert!(read_request(&mut fine.as_bytes()).unwrap().is_ok());
+    }
+}
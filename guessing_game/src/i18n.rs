@@ -0,0 +1,1408 @@
+/*
+ * Message catalog for every mode that takes `--lang`, in English, German and Spanish:
+ * the round itself in line mode and full screen, and the timed, evil, daily, tournament
+ * and LAN modes on top of it. The language comes from `--lang`, otherwise from LC_ALL,
+ * LC_MESSAGES or LANG like other programs, and falls back to English.
+ *
+ * Every language is one exhaustive match over `Text`, so a message added without
+ * translations does not compile. The tests below also render every key in every language.
+ */
+
+use crate::{
+    coach::{GuessReport, Rating},
+    config::Difficulty,
+    daily::{DailyResult, Date},
+    net::Event,
+    parse::ParseError,
+    tournament::Status,
+};
+use std::{cmp::Ordering, env, fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    English,
+    German,
+    Spanish,
+}
+
+/// Everything the player can be told, with whatever goes into the sentence.
+#[derive(Debug, Clone, Copy)]
+pub enum Text<'a> {
+    GuessTheNumber,
+    Range {
+        difficulty: Difficulty,
+        min: i128,
+        max: i128,
+    },
+    Commitment(&'a str),
+    CommitmentNote,
+    Prompt {
+        attempts_left: Option<u32>,
+    },
+    TooSmall,
+    TooBig,
+    YouWin,
+    Won {
+        attempts: u32,
+    },
+    Lost {
+        secret: i128,
+    },
+    /// A won round of the time attack.
+    TimedWon {
+        attempts: u32,
+        seconds: f64,
+        score: u64,
+    },
+    TimeUp {
+        secret: i128,
+    },
+    /// What the coach says about one guess.
+    Coach(&'a GuessReport),
+    /// A guess that did not parse, with the reason.
+    Invalid(&'a ParseError),
+    ParseError(&'a ParseError),
+    PastGuess {
+        attempt: usize,
+        guess: i128,
+        ordering: Ordering,
+    },
+    NoMoreInput {
+        attempts: u32,
+        secret: i128,
+    },
+    Reveal {
+        secret: i128,
+        salt: &'a str,
+        commitment: &'a str,
+    },
+    Resuming,
+    SavePrompt,
+    Abandoned {
+        secret: i128,
+    },
+    Saved {
+        path: &'a str,
+    },
+    Bye,
+    ScreenHelp,
+    Attempts {
+        attempts: u32,
+        max: Option<u32>,
+    },
+    Time {
+        seconds: u64,
+    },
+    StillPossible {
+        low: i128,
+        high: i128,
+        count: u128,
+    },
+    History,
+    PressAnyKey,
+    TimedIntro {
+        rounds: u32,
+        seconds: f64,
+        per_session: bool,
+        min: i128,
+        max: i128,
+    },
+    TimedRound {
+        round: u32,
+        rounds: u32,
+    },
+    TimedPrompt {
+        seconds_left: u64,
+    },
+    SessionStopped,
+    EvilIntro,
+    EvilRange {
+        min: i128,
+        max: i128,
+    },
+    EvilPrompt {
+        possible: u128,
+    },
+    EvilBye,
+    /// How many guesses binary search needs at most, after a won evil round.
+    EvilOptimal {
+        attempts: u32,
+    },
+    DailyIntro {
+        date: Date,
+        min: i128,
+        max: i128,
+    },
+    DailyNotToday,
+    DailyAlreadyPlayed {
+        name: &'a str,
+        result: DailyResult,
+    },
+    DailyGivingUp {
+        secret: i128,
+    },
+    DailyStoppedEarly,
+    /// Stopped after some guesses, but the day is still open so the secret stays hidden.
+    DailyStoppedHidden,
+    DailyStartedElsewhere,
+    TournamentIntro {
+        rounds: u32,
+        players: &'a [String],
+        shared: bool,
+    },
+    TournamentRules {
+        min: i128,
+        max: i128,
+        attempts: u32,
+    },
+    TournamentRound {
+        round: u32,
+    },
+    PlayerPrompt {
+        player: &'a str,
+        attempts_left: Option<u32>,
+    },
+    TournamentStopped,
+    Forfeits {
+        player: &'a str,
+    },
+    PlayerFound {
+        player: &'a str,
+        attempts: u32,
+    },
+    PlayerOut {
+        player: &'a str,
+    },
+    RoundOver {
+        round: u32,
+    },
+    PlayerResult {
+        player: &'a str,
+        status: Status,
+        secret: i128,
+    },
+    /// Nobody, one winner or a draw.
+    RoundWinners(&'a [String]),
+    FinalStandings,
+    /// What happened in a LAN round.
+    Event(&'a Event),
+}
+
+impl Lang {
+    pub const ALL: [Lang; 3] = [Lang::English, Lang::German, Lang::Spanish];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Lang::English => "en",
+            Lang::German => "de",
+            Lang::Spanish => "es",
+        }
+    }
+
+    /// The language of the environment, English if it is not set or not supported.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether `answer` means yes, in this language or in English.
+    pub fn is_yes(&self, answer: &str) -> bool {
+        let answer = answer.trim().to_lowercase();
+        let words: &[&str] = match self {
+            Lang::English => &[],
+            Lang::German => &["j", "ja"],
+            Lang::Spanish => &["s", "si", "sí"],
+        };
+        ["y", "yes"].contains(&answer.as_str()) || words.contains(&answer.as_str())
+    }
+
+    pub fn text(&self, text: Text) -> String {
+        match self {
+            Lang::English => english(text),
+            Lang::German => german(text),
+            Lang::Spanish => spanish(text),
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Lang {
+    type Err = String;
+
+    /// Takes codes like `de` as well as locales like `de_DE.UTF-8`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = s
+            .split(['_', '.', '-', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match code.as_str() {
+            "en" | "c" | "posix" => Ok(Lang::English),
+            "de" => Ok(Lang::German),
+            "es" => Ok(Lang::Spanish),
+            _ => Err(format!(
+                "Unknown language `{s}`, expected one of {}",
+                Lang::ALL.map(|lang| lang.code()).join(", ")
+            )),
+        }
+    }
+}
+
+fn english(text: Text) -> String {
+    match text {
+        Text::GuessTheNumber => String::from("Guess the number!"),
+        Text::Range {
+            difficulty,
+            min,
+            max,
+        } => format!("Difficulty: {difficulty}, range {min}..={max}"),
+        Text::Commitment(commitment) => format!("Commitment: {commitment}"),
+        Text::CommitmentNote => String::from(
+            "(SHA-256 of the secret number and a salt, both are revealed when the round ends)",
+        ),
+        Text::Prompt {
+            attempts_left: Some(left),
+        } => format!("Please input your guess! ({left} attempts left)"),
+        Text::Prompt {
+            attempts_left: None,
+        } => String::from("Please input your guess!"),
+        Text::TooSmall => String::from("Too Small!"),
+        Text::TooBig => String::from("Too Big!!"),
+        Text::YouWin => String::from("You Win!"),
+        Text::Won { attempts } => format!("You Win! It took you {attempts} attempts."),
+        Text::Lost { secret } => format!("Out of attempts! The secret number was {secret}."),
+        Text::TimedWon {
+            attempts,
+            seconds,
+            score,
+        } => format!("You Win! {attempts} attempts in {seconds:.1}s, {score} points."),
+        Text::TimeUp { secret } => format!("Time's up! The secret number was {secret}."),
+        Text::Coach(report) => {
+            let left = match report.after.len() {
+                1 => String::from("1 number left"),
+                left => format!("{left} numbers left"),
+            };
+            format!(
+                "Coach: {left} ({}..={}). Gained {:.2} bits, expected {:.2} of the best {:.2} (guessing {}), {}.",
+                report.after.low,
+                report.after.high,
+                report.gained,
+                report.expected,
+                report.best,
+                report.best_guess(),
+                match report.rating() {
+                    Rating::Excellent => "excellent",
+                    Rating::Good => "good",
+                    Rating::Fair => "fair",
+                    Rating::Poor => "poor",
+                    Rating::Wasted => "wasted",
+                }
+            )
+        }
+        Text::Invalid(error) => format!("Aah! {} Try again!", english(Text::ParseError(error))),
+        Text::ParseError(error) => match error {
+            ParseError::Empty => String::from("You did not type anything."),
+            ParseError::UnknownToken { token, column } => {
+                format!("I did not understand `{token}` at column {column}.")
+            }
+            ParseError::Unexpected { token, column } => {
+                format!("`{token}` at column {column} does not fit there.")
+            }
+            ParseError::UnexpectedEnd => String::from("The guess stops in the middle."),
+            ParseError::DivisionByZero { column } => {
+                format!("Division by zero at column {column}.")
+            }
+            ParseError::NotWhole { column } => {
+                format!("The division at column {column} does not give a whole number.")
+            }
+            ParseError::Overflow => String::from("That number is too big."),
+            ParseError::TooDeep { column } => {
+                format!("Too much nesting at column {column}.")
+            }
+        },
+        Text::PastGuess {
+            attempt,
+            guess,
+            ordering,
+        } => format!(
+            "#{attempt} you guessed {guess}: {}",
+            english(ordering_text(ordering))
+        ),
+        Text::NoMoreInput { attempts, secret } => format!(
+            "No more input, ending the round after {attempts} attempts. The secret number was {secret}."
+        ),
+        Text::Reveal {
+            secret,
+            salt,
+            commitment,
+        } => format!(
+            "Secret {secret}, salt {salt}. Check them with: guessing_game verify {commitment} {secret} {salt}"
+        ),
+        Text::Resuming => String::from("Resuming your saved round."),
+        Text::SavePrompt => {
+            String::from("Save this round so you can continue it with --resume? (y/n)")
+        }
+        Text::Abandoned { secret } => {
+            format!("Round abandoned, the secret number was {secret}.")
+        }
+        Text::Saved { path } => format!("Saved to {path}, see you later!"),
+        Text::Bye => String::from("Bye!"),
+        Text::ScreenHelp => String::from("Type a number and press Enter, Esc quits."),
+        Text::Attempts {
+            attempts,
+            max: Some(max),
+        } => format!("Attempts {attempts}/{max}"),
+        Text::Attempts {
+            attempts,
+            max: None,
+        } => format!("Attempts {attempts}"),
+        Text::Time { seconds } => format!("Time {:02}:{:02}", seconds / 60, seconds % 60),
+        Text::StillPossible { low, high, count } => {
+            format!("Still possible: {low}..={high} ({count} numbers)")
+        }
+        Text::History => String::from("History"),
+        Text::PressAnyKey => String::from("Press any key."),
+        Text::TimedIntro {
+            rounds,
+            seconds,
+            per_session,
+            min,
+            max,
+        } => format!(
+            "Time attack! {rounds} rounds, {seconds}s {}, range {min}..={max}",
+            if per_session {
+                "for the whole session"
+            } else {
+                "per round"
+            }
+        ),
+        Text::TimedRound { round, rounds } => format!("Round {round}/{rounds}, guess the number!"),
+        Text::TimedPrompt { seconds_left } => {
+            format!("Please input your guess! ({seconds_left}s left)")
+        }
+        Text::SessionStopped => String::from("Session stopped."),
+        Text::EvilIntro => String::from("Guess the number! Careful, this host cheats."),
+        Text::EvilRange { min, max } => format!(
+            "Range {min}..={max}, I only pick my number once you leave me no choice."
+        ),
+        Text::EvilPrompt { possible } => {
+            format!("Please input your guess! ({possible} numbers still possible)")
+        }
+        Text::EvilBye => String::from("Bye! I never had to pick a number."),
+        Text::EvilOptimal { attempts } => {
+            format!("Binary search never needs more than {attempts} here.")
+        }
+        Text::DailyIntro { date, min, max } => {
+            format!("Daily challenge for {date}: guess the number between {min} and {max}!")
+        }
+        Text::DailyNotToday => {
+            String::from("That is not today's puzzle, this round is not scored.")
+        }
+        Text::DailyAlreadyPlayed { name, result } => format!(
+            "You already played today as {name} ({}), this round is not scored.",
+            match result {
+                DailyResult::Playing => String::from("unfinished"),
+                DailyResult::Won { attempts } => format!("won in {attempts}"),
+                DailyResult::Lost => String::from("lost"),
+            }
+        ),
+        Text::DailyGivingUp { secret } => format!("Giving up? The number was {secret}."),
+        Text::DailyStoppedEarly => {
+            String::from("Stopped before the first guess, today's puzzle is still open.")
+        }
+        Text::DailyStoppedHidden => {
+            String::from("Stopped, the number stays hidden because today's puzzle is still open.")
+        }
+        Text::DailyStartedElsewhere => {
+            String::from("You started today's puzzle somewhere else, this round is not scored.")
+        }
+        Text::TournamentIntro {
+            rounds,
+            players,
+            shared,
+        } => format!(
+            "Tournament! {rounds} rounds, {}, {}.",
+            players.join(" vs "),
+            if shared {
+                "all hunting the same number"
+            } else {
+                "each on their own number"
+            }
+        ),
+        Text::TournamentRules { min, max, attempts } => format!(
+            "Range {min}..={max}, at most {attempts} attempts each per round. Type `forfeit` to give up a round."
+        ),
+        Text::TournamentRound { round } => format!("Round {round}"),
+        Text::PlayerPrompt {
+            player,
+            attempts_left: Some(left),
+        } => format!("{player}, your guess! ({left} attempts left)"),
+        Text::PlayerPrompt {
+            player,
+            attempts_left: None,
+        } => format!("{player}, your guess!"),
+        Text::TournamentStopped => String::from("Tournament stopped early."),
+        Text::Forfeits { player } => format!("{player} forfeits this round."),
+        Text::PlayerFound { player, attempts } => format!("{player} got it in {attempts} attempts!"),
+        Text::PlayerOut { player } => format!("{player} is out of attempts."),
+        Text::RoundOver { round } => format!("Round {round} is over."),
+        Text::PlayerResult {
+            player,
+            status,
+            secret,
+        } => format!(
+            "  {player}: {}, the number was {secret}",
+            match status {
+                Status::Found { attempts } => format!("found it in {attempts} attempts"),
+                Status::OutOfAttempts => String::from("ran out of attempts"),
+                Status::Forfeited => String::from("forfeited"),
+                Status::Playing => String::from("did not finish"),
+            }
+        ),
+        Text::RoundWinners(winners) => match winners {
+            [] => String::from("Nobody wins this round."),
+            [winner] => format!("{winner} wins the round!"),
+            winners => format!("Draw between {}.", winners.join(" and ")),
+        },
+        Text::FinalStandings => String::from("Final standings"),
+        Text::Event(event) => match event {
+            Event::Welcome {
+                min,
+                max,
+                max_attempts: Some(limit),
+            } => format!("Guess the number between {min} and {max}, {limit} attempts each!"),
+            Event::Welcome {
+                min,
+                max,
+                max_attempts: None,
+            } => format!("Guess the number between {min} and {max}!"),
+            Event::Joined { name } => format!("{name} joined the round"),
+            Event::Guessed {
+                name,
+                guess,
+                ordering,
+            } => format!(
+                "{name} guessed {guess}: {}",
+                match ordering {
+                    Ordering::Equal => String::from("Correct!"),
+                    ordering => english(ordering_text(*ordering)),
+                }
+            ),
+            Event::Out { name } => format!("{name} is out of attempts"),
+            Event::Left { name } => format!("{name} left the round"),
+            Event::Winner {
+                name,
+                attempts,
+                secret,
+            } => format!(
+                "{name} wins! The secret number was {secret}, found in {attempts} attempts."
+            ),
+            Event::Over { secret } => {
+                format!("Everybody is out of attempts, the secret number was {secret}.")
+            }
+            Event::Error { message } => format!("Error: {message}"),
+        },
+    }
+}
+
+fn german(text: Text) -> String {
+    match text {
+        Text::GuessTheNumber => String::from("Errate die Zahl!"),
+        Text::Range {
+            difficulty,
+            min,
+            max,
+        } => format!("Schwierigkeit: {difficulty}, Bereich {min}..={max}"),
+        Text::Commitment(commitment) => format!("Festlegung: {commitment}"),
+        Text::CommitmentNote => String::from(
+            "(SHA-256 der geheimen Zahl und eines Salts, beide werden am Ende der Runde aufgedeckt)",
+        ),
+        Text::Prompt {
+            attempts_left: Some(left),
+        } => format!("Bitte gib deinen Tipp ein! (noch {left} Versuche)"),
+        Text::Prompt {
+            attempts_left: None,
+        } => String::from("Bitte gib deinen Tipp ein!"),
+        Text::TooSmall => String::from("Zu klein!"),
+        Text::TooBig => String::from("Zu groß!!"),
+        Text::YouWin => String::from("Gewonnen!"),
+        Text::Won { attempts } => format!("Gewonnen! Du hast {attempts} Versuche gebraucht."),
+        Text::Lost { secret } => format!("Keine Versuche mehr! Die geheime Zahl war {secret}."),
+        Text::TimedWon {
+            attempts,
+            seconds,
+            score,
+        } => format!("Gewonnen! {attempts} Versuche in {seconds:.1}s, {score} Punkte."),
+        Text::TimeUp { secret } => format!("Die Zeit ist um! Die geheime Zahl war {secret}."),
+        Text::Coach(report) => {
+            let left = match report.after.len() {
+                1 => String::from("1 Zahl übrig"),
+                left => format!("{left} Zahlen übrig"),
+            };
+            format!(
+                "Trainer: {left} ({}..={}). {:.2} Bits gewonnen, erwartet waren {:.2} von bestenfalls {:.2} (mit {}), {}.",
+                report.after.low,
+                report.after.high,
+                report.gained,
+                report.expected,
+                report.best,
+                report.best_guess(),
+                match report.rating() {
+                    Rating::Excellent => "ausgezeichnet",
+                    Rating::Good => "gut",
+                    Rating::Fair => "ordentlich",
+                    Rating::Poor => "schwach",
+                    Rating::Wasted => "verschenkt",
+                }
+            )
+        }
+        Text::Invalid(error) => format!(
+            "Hoppla! {} Versuch es noch einmal!",
+            german(Text::ParseError(error))
+        ),
+        Text::ParseError(error) => match error {
+            ParseError::Empty => String::from("Du hast nichts eingegeben."),
+            ParseError::UnknownToken { token, column } => {
+                format!("Ich habe `{token}` an Spalte {column} nicht verstanden.")
+            }
+            ParseError::Unexpected { token, column } => {
+                format!("`{token}` an Spalte {column} passt dort nicht hin.")
+            }
+            ParseError::UnexpectedEnd => String::from("Der Tipp hört mittendrin auf."),
+            ParseError::DivisionByZero { column } => {
+                format!("Division durch null an Spalte {column}.")
+            }
+            ParseError::NotWhole { column } => {
+                format!("Die Division an Spalte {column} ergibt keine ganze Zahl.")
+            }
+            ParseError::Overflow => String::from("Diese Zahl ist zu groß."),
+            ParseError::TooDeep { column } => {
+                format!("Zu tief verschachtelt an Spalte {column}.")
+            }
+        },
+        Text::PastGuess {
+            attempt,
+            guess,
+            ordering,
+        } => format!(
+            "#{attempt} du hast {guess} geraten: {}",
+            german(ordering_text(ordering))
+        ),
+        Text::NoMoreInput { attempts, secret } => format!(
+            "Keine Eingabe mehr, die Runde endet nach {attempts} Versuchen. Die geheime Zahl war {secret}."
+        ),
+        Text::Reveal {
+            secret,
+            salt,
+            commitment,
+        } => format!(
+            "Geheime Zahl {secret}, Salt {salt}. Prüfen mit: guessing_game verify {commitment} {secret} {salt}"
+        ),
+        Text::Resuming => String::from("Deine gespeicherte Runde geht weiter."),
+        Text::SavePrompt => {
+            String::from("Diese Runde speichern, um sie mit --resume fortzusetzen? (j/n)")
+        }
+        Text::Abandoned { secret } => {
+            format!("Runde abgebrochen, die geheime Zahl war {secret}.")
+        }
+        Text::Saved { path } => format!("Gespeichert in {path}, bis später!"),
+        Text::Bye => String::from("Tschüss!"),
+        Text::ScreenHelp => String::from("Gib eine Zahl ein und drücke Enter, Esc beendet."),
+        Text::Attempts {
+            attempts,
+            max: Some(max),
+        } => format!("Versuche {attempts}/{max}"),
+        Text::Attempts {
+            attempts,
+            max: None,
+        } => format!("Versuche {attempts}"),
+        Text::Time { seconds } => format!("Zeit {:02}:{:02}", seconds / 60, seconds % 60),
+        Text::StillPossible { low, high, count } => {
+            format!("Noch möglich: {low}..={high} ({count} Zahlen)")
+        }
+        Text::History => String::from("Verlauf"),
+        Text::PressAnyKey => String::from("Beliebige Taste drücken."),
+        Text::TimedIntro {
+            rounds,
+            seconds,
+            per_session,
+            min,
+            max,
+        } => format!(
+            "Gegen die Uhr! {rounds} Runden, {seconds}s {}, Bereich {min}..={max}",
+            if per_session {
+                "für die ganze Sitzung"
+            } else {
+                "pro Runde"
+            }
+        ),
+        Text::TimedRound { round, rounds } => format!("Runde {round}/{rounds}, errate die Zahl!"),
+        Text::TimedPrompt { seconds_left } => {
+            format!("Bitte gib deinen Tipp ein! (noch {seconds_left}s)")
+        }
+        Text::SessionStopped => String::from("Sitzung beendet."),
+        Text::EvilIntro => String::from("Errate die Zahl! Vorsicht, dieser Gastgeber schummelt."),
+        Text::EvilRange { min, max } => format!(
+            "Bereich {min}..={max}, ich lege mich erst fest, wenn du mir keine Wahl mehr lässt."
+        ),
+        Text::EvilPrompt { possible } => {
+            format!("Bitte gib deinen Tipp ein! ({possible} Zahlen noch möglich)")
+        }
+        Text::EvilBye => String::from("Tschüss! Ich musste mich nie auf eine Zahl festlegen."),
+        Text::EvilOptimal { attempts } => {
+            format!("Binäre Suche braucht hier nie mehr als {attempts}.")
+        }
+        Text::DailyIntro { date, min, max } => {
+            format!("Tagesrätsel vom {date}: errate die Zahl zwischen {min} und {max}!")
+        }
+        Text::DailyNotToday => {
+            String::from("Das ist nicht das heutige Rätsel, diese Runde zählt nicht.")
+        }
+        Text::DailyAlreadyPlayed { name, result } => format!(
+            "Du hast heute schon als {name} gespielt ({}), diese Runde zählt nicht.",
+            match result {
+                DailyResult::Playing => String::from("nicht beendet"),
+                DailyResult::Won { attempts } => format!("gewonnen in {attempts}"),
+                DailyResult::Lost => String::from("verloren"),
+            }
+        ),
+        Text::DailyGivingUp { secret } => format!("Aufgeben? Die Zahl war {secret}."),
+        Text::DailyStoppedEarly => {
+            String::from("Vor dem ersten Tipp beendet, das heutige Rätsel ist noch offen.")
+        }
+        Text::DailyStoppedHidden => String::from(
+            "Beendet, die Zahl bleibt geheim, weil das heutige Rätsel noch offen ist.",
+        ),
+        Text::DailyStartedElsewhere => String::from(
+            "Du hast das heutige Rätsel schon woanders begonnen, diese Runde zählt nicht.",
+        ),
+        Text::TournamentIntro {
+            rounds,
+            players,
+            shared,
+        } => format!(
+            "Turnier! {rounds} Runden, {}, {}.",
+            players.join(" gegen "),
+            if shared {
+                "alle jagen dieselbe Zahl"
+            } else {
+                "jeder mit seiner eigenen Zahl"
+            }
+        ),
+        Text::TournamentRules { min, max, attempts } => format!(
+            "Bereich {min}..={max}, höchstens {attempts} Versuche pro Runde und Person. Mit `forfeit` gibst du eine Runde auf."
+        ),
+        Text::TournamentRound { round } => format!("Runde {round}"),
+        Text::PlayerPrompt {
+            player,
+            attempts_left: Some(left),
+        } => format!("{player}, dein Tipp! (noch {left} Versuche)"),
+        Text::PlayerPrompt {
+            player,
+            attempts_left: None,
+        } => format!("{player}, dein Tipp!"),
+        Text::TournamentStopped => String::from("Turnier vorzeitig beendet."),
+        Text::Forfeits { player } => format!("{player} gibt diese Runde auf."),
+        Text::PlayerFound { player, attempts } => {
+            format!("{player} hat sie in {attempts} Versuchen gefunden!")
+        }
+        Text::PlayerOut { player } => format!("{player} hat keine Versuche mehr."),
+        Text::RoundOver { round } => format!("Runde {round} ist vorbei."),
+        Text::PlayerResult {
+            player,
+            status,
+            secret,
+        } => format!(
+            "  {player}: {}, die Zahl war {secret}",
+            match status {
+                Status::Found { attempts } => format!("in {attempts} Versuchen gefunden"),
+                Status::OutOfAttempts => String::from("keine Versuche mehr"),
+                Status::Forfeited => String::from("aufgegeben"),
+                Status::Playing => String::from("nicht fertig geworden"),
+            }
+        ),
+        Text::RoundWinners(winners) => match winners {
+            [] => String::from("Niemand gewinnt diese Runde."),
+            [winner] => format!("{winner} gewinnt die Runde!"),
+            winners => format!("Unentschieden zwischen {}.", winners.join(" und ")),
+        },
+        Text::FinalStandings => String::from("Endstand"),
+        Text::Event(event) => match event {
+            Event::Welcome {
+                min,
+                max,
+                max_attempts: Some(limit),
+            } => format!("Errate die Zahl zwischen {min} und {max}, {limit} Versuche für jeden!"),
+            Event::Welcome {
+                min,
+                max,
+                max_attempts: None,
+            } => format!("Errate die Zahl zwischen {min} und {max}!"),
+            Event::Joined { name } => format!("{name} spielt mit"),
+            Event::Guessed {
+                name,
+                guess,
+                ordering,
+            } => format!(
+                "{name} hat {guess} geraten: {}",
+                match ordering {
+                    Ordering::Equal => String::from("Richtig!"),
+                    ordering => german(ordering_text(*ordering)),
+                }
+            ),
+            Event::Out { name } => format!("{name} hat keine Versuche mehr"),
+            Event::Left { name } => format!("{name} hat die Runde verlassen"),
+            Event::Winner {
+                name,
+                attempts,
+                secret,
+            } => format!(
+                "{name} gewinnt! Die geheime Zahl war {secret}, gefunden in {attempts} Versuchen."
+            ),
+            Event::Over { secret } => {
+                format!("Niemand hat mehr Versuche, die geheime Zahl war {secret}.")
+            }
+            Event::Error { message } => format!("Fehler: {message}"),
+        },
+    }
+}
+
+fn spanish(text: Text) -> String {
+    match text {
+        Text::GuessTheNumber => String::from("¡Adivina el número!"),
+        Text::Range {
+            difficulty,
+            min,
+            max,
+        } => format!("Dificultad: {difficulty}, rango {min}..={max}"),
+        Text::Commitment(commitment) => format!("Compromiso: {commitment}"),
+        Text::CommitmentNote => String::from(
+            "(SHA-256 del número secreto y una sal, ambos se revelan al terminar la ronda)",
+        ),
+        Text::Prompt {
+            attempts_left: Some(left),
+        } => format!("¡Escribe tu intento! (quedan {left} intentos)"),
+        Text::Prompt {
+            attempts_left: None,
+        } => String::from("¡Escribe tu intento!"),
+        Text::TooSmall => String::from("¡Muy pequeño!"),
+        Text::TooBig => String::from("¡¡Muy grande!!"),
+        Text::YouWin => String::from("¡Ganaste!"),
+        Text::Won { attempts } => format!("¡Ganaste! Te llevó {attempts} intentos."),
+        Text::Lost { secret } => format!("¡Sin intentos! El número secreto era {secret}."),
+        Text::TimedWon {
+            attempts,
+            seconds,
+            score,
+        } => format!("¡Ganaste! {attempts} intentos en {seconds:.1}s, {score} puntos."),
+        Text::TimeUp { secret } => format!("¡Se acabó el tiempo! El número secreto era {secret}."),
+        Text::Coach(report) => {
+            let left = match report.after.len() {
+                1 => String::from("queda 1 número"),
+                left => format!("quedan {left} números"),
+            };
+            format!(
+                "Entrenador: {left} ({}..={}). Ganaste {:.2} bits, se esperaban {:.2} de los {:.2} posibles (con {}), {}.",
+                report.after.low,
+                report.after.high,
+                report.gained,
+                report.expected,
+                report.best,
+                report.best_guess(),
+                match report.rating() {
+                    Rating::Excellent => "excelente",
+                    Rating::Good => "bueno",
+                    Rating::Fair => "regular",
+                    Rating::Poor => "malo",
+                    Rating::Wasted => "desperdiciado",
+                }
+            )
+        }
+        Text::Invalid(error) => format!(
+            "¡Ay! {} ¡Inténtalo de nuevo!",
+            spanish(Text::ParseError(error))
+        ),
+        Text::ParseError(error) => match error {
+            ParseError::Empty => String::from("No escribiste nada."),
+            ParseError::UnknownToken { token, column } => {
+                format!("No entendí `{token}` en la columna {column}.")
+            }
+            ParseError::Unexpected { token, column } => {
+                format!("`{token}` en la columna {column} no encaja ahí.")
+            }
+            ParseError::UnexpectedEnd => String::from("El intento se corta a la mitad."),
+            ParseError::DivisionByZero { column } => {
+                format!("División por cero en la columna {column}.")
+            }
+            ParseError::NotWhole { column } => {
+                format!("La división en la columna {column} no da un número entero.")
+            }
+            ParseError::Overflow => String::from("Ese número es demasiado grande."),
+            ParseError::TooDeep { column } => {
+                format!("Demasiado anidado en la columna {column}.")
+            }
+        },
+        Text::PastGuess {
+            attempt,
+            guess,
+            ordering,
+        } => format!(
+            "#{attempt} dijiste {guess}: {}",
+            spanish(ordering_text(ordering))
+        ),
+        Text::NoMoreInput { attempts, secret } => format!(
+            "No hay más entrada, la ronda termina tras {attempts} intentos. El número secreto era {secret}."
+        ),
+        Text::Reveal {
+            secret,
+            salt,
+            commitment,
+        } => format!(
+            "Secreto {secret}, sal {salt}. Compruébalo con: guessing_game verify {commitment} {secret} {salt}"
+        ),
+        Text::Resuming => String::from("Continuando tu ronda guardada."),
+        Text::SavePrompt => {
+            String::from("¿Guardar esta ronda para seguirla con --resume? (s/n)")
+        }
+        Text::Abandoned { secret } => {
+            format!("Ronda abandonada, el número secreto era {secret}.")
+        }
+        Text::Saved { path } => format!("Guardada en {path}, ¡hasta luego!"),
+        Text::Bye => String::from("¡Adiós!"),
+        Text::ScreenHelp => String::from("Escribe un número y pulsa Enter, Esc sale."),
+        Text::Attempts {
+            attempts,
+            max: Some(max),
+        } => format!("Intentos {attempts}/{max}"),
+        Text::Attempts {
+            attempts,
+            max: None,
+        } => format!("Intentos {attempts}"),
+        Text::Time { seconds } => format!("Tiempo {:02}:{:02}", seconds / 60, seconds % 60),
+        Text::StillPossible { low, high, count } => {
+            format!("Aún posible: {low}..={high} ({count} números)")
+        }
+        Text::History => String::from("Historial"),
+        Text::PressAnyKey => String::from("Pulsa cualquier tecla."),
+        Text::TimedIntro {
+            rounds,
+            seconds,
+            per_session,
+            min,
+            max,
+        } => format!(
+            "¡Contrarreloj! {rounds} rondas, {seconds}s {}, rango {min}..={max}",
+            if per_session {
+                "para toda la sesión"
+            } else {
+                "por ronda"
+            }
+        ),
+        Text::TimedRound { round, rounds } => {
+            format!("Ronda {round}/{rounds}, ¡adivina el número!")
+        }
+        Text::TimedPrompt { seconds_left } => {
+            format!("¡Escribe tu intento! (quedan {seconds_left}s)")
+        }
+        Text::SessionStopped => String::from("Sesión detenida."),
+        Text::EvilIntro => String::from("¡Adivina el número! Cuidado, este anfitrión hace trampa."),
+        Text::EvilRange { min, max } => format!(
+            "Rango {min}..={max}, solo elijo mi número cuando no me dejes otra opción."
+        ),
+        Text::EvilPrompt { possible } => {
+            format!("¡Escribe tu intento! (aún son posibles {possible} números)")
+        }
+        Text::EvilBye => String::from("¡Adiós! Nunca tuve que elegir un número."),
+        Text::EvilOptimal { attempts } => {
+            format!("La búsqueda binaria nunca necesita más de {attempts} aquí.")
+        }
+        Text::DailyIntro { date, min, max } => {
+            format!("Reto diario del {date}: ¡adivina el número entre {min} y {max}!")
+        }
+        Text::DailyNotToday => {
+            String::from("Ese no es el reto de hoy, esta ronda no puntúa.")
+        }
+        Text::DailyAlreadyPlayed { name, result } => format!(
+            "Ya jugaste hoy como {name} ({}), esta ronda no puntúa.",
+            match result {
+                DailyResult::Playing => String::from("sin terminar"),
+                DailyResult::Won { attempts } => format!("ganado en {attempts}"),
+                DailyResult::Lost => String::from("perdido"),
+            }
+        ),
+        Text::DailyGivingUp { secret } => format!("¿Te rindes? El número era {secret}."),
+        Text::DailyStoppedEarly => {
+            String::from("Detenido antes del primer intento, el reto de hoy sigue abierto.")
+        }
+        Text::DailyStoppedHidden => String::from(
+            "Detenido, el número sigue oculto porque el reto de hoy sigue abierto.",
+        ),
+        Text::DailyStartedElsewhere => String::from(
+            "Empezaste el reto de hoy en otro sitio, esta ronda no puntúa.",
+        ),
+        Text::TournamentIntro {
+            rounds,
+            players,
+            shared,
+        } => format!(
+            "¡Torneo! {rounds} rondas, {}, {}.",
+            players.join(" contra "),
+            if shared {
+                "todos buscan el mismo número"
+            } else {
+                "cada uno con su propio número"
+            }
+        ),
+        Text::TournamentRules { min, max, attempts } => format!(
+            "Rango {min}..={max}, como mucho {attempts} intentos por persona y ronda. Escribe `forfeit` para abandonar una ronda."
+        ),
+        Text::TournamentRound { round } => format!("Ronda {round}"),
+        Text::PlayerPrompt {
+            player,
+            attempts_left: Some(left),
+        } => format!("{player}, ¡tu intento! (quedan {left} intentos)"),
+        Text::PlayerPrompt {
+            player,
+            attempts_left: None,
+        } => format!("{player}, ¡tu intento!"),
+        Text::TournamentStopped => String::from("Torneo detenido antes de tiempo."),
+        Text::Forfeits { player } => format!("{player} abandona esta ronda."),
+        Text::PlayerFound { player, attempts } => {
+            format!("¡{player} lo encontró en {attempts} intentos!")
+        }
+        Text::PlayerOut { player } => format!("{player} se quedó sin intentos."),
+        Text::RoundOver { round } => format!("La ronda {round} ha terminado."),
+        Text::PlayerResult {
+            player,
+            status,
+            secret,
+        } => format!(
+            "  {player}: {}, el número era {secret}",
+            match status {
+                Status::Found { attempts } => format!("lo encontró en {attempts} intentos"),
+                Status::OutOfAttempts => String::from("se quedó sin intentos"),
+                Status::Forfeited => String::from("abandonó"),
+                Status::Playing => String::from("no terminó"),
+            }
+        ),
+        Text::RoundWinners(winners) => match winners {
+            [] => String::from("Nadie gana esta ronda."),
+            [winner] => format!("¡{winner} gana la ronda!"),
+            winners => format!("Empate entre {}.", winners.join(" y ")),
+        },
+        Text::FinalStandings => String::from("Clasificación final"),
+        Text::Event(event) => match event {
+            Event::Welcome {
+                min,
+                max,
+                max_attempts: Some(limit),
+            } => format!("¡Adivina el número entre {min} y {max}, {limit} intentos cada uno!"),
+            Event::Welcome {
+                min,
+                max,
+                max_attempts: None,
+            } => format!("¡Adivina el número entre {min} y {max}!"),
+            Event::Joined { name } => format!("{name} se unió a la ronda"),
+            Event::Guessed {
+                name,
+                guess,
+                ordering,
+            } => format!(
+                "{name} probó {guess}: {}",
+                match ordering {
+                    Ordering::Equal => String::from("¡Correcto!"),
+                    ordering => spanish(ordering_text(*ordering)),
+                }
+            ),
+            Event::Out { name } => format!("{name} se quedó sin intentos"),
+            Event::Left { name } => format!("{name} dejó la ronda"),
+            Event::Winner {
+                name,
+                attempts,
+                secret,
+            } => format!(
+                "¡{name} gana! El número secreto era {secret}, encontrado en {attempts} intentos."
+            ),
+            Event::Over { secret } => {
+                format!("Nadie tiene más intentos, el número secreto era {secret}.")
+            }
+            Event::Error { message } => format!("Error del servidor: {message}"),
+        },
+    }
+}
+
+/// The answer to a guess as `TooSmall`, `TooBig` or `YouWin`.
+pub fn ordering_text(ordering: Ordering) -> Text<'static> {
+    match ordering {
+        Ordering::Less => Text::TooSmall,
+        Ordering::Greater => Text::TooBig,
+        Ordering::Equal => Text::YouWin,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coach::Coach, config::Config};
+
+    const VARIANTS: usize = 58;
+
+    /// Position of the variant, the match breaks the build when a variant is added.
+    fn variant(text: &Text) -> usize {
+        match text {
+            Text::GuessTheNumber => 0,
+            Text::Range { .. } => 1,
+            Text::Commitment(_) => 2,
+            Text::CommitmentNote => 3,
+            Text::Prompt { .. } => 4,
+            Text::TooSmall => 5,
+            Text::TooBig => 6,
+            Text::YouWin => 7,
+            Text::Won { .. } => 8,
+            Text::Lost { .. } => 9,
+            Text::Invalid(_) => 10,
+            Text::ParseError(_) => 11,
+            Text::PastGuess { .. } => 12,
+            Text::NoMoreInput { .. } => 13,
+            Text::Reveal { .. } => 14,
+            Text::Resuming => 15,
+            Text::SavePrompt => 16,
+            Text::Abandoned { .. } => 17,
+            Text::Saved { .. } => 18,
+            Text::Bye => 19,
+            Text::ScreenHelp => 20,
+            Text::Attempts { .. } => 21,
+            Text::Time { .. } => 22,
+            Text::StillPossible { .. } => 23,
+            Text::History => 24,
+            Text::PressAnyKey => 25,
+            Text::TimedWon { .. } => 26,
+            Text::TimeUp { .. } => 27,
+            Text::Coach(_) => 28,
+            Text::TimedIntro { .. } => 29,
+            Text::TimedRound { .. } => 30,
+            Text::TimedPrompt { .. } => 31,
+            Text::SessionStopped => 32,
+            Text::EvilIntro => 33,
+            Text::EvilRange { .. } => 34,
+            Text::EvilPrompt { .. } => 35,
+            Text::EvilBye => 36,
+            Text::EvilOptimal { .. } => 37,
+            Text::DailyIntro { .. } => 38,
+            Text::DailyNotToday => 39,
+            Text::DailyAlreadyPlayed { .. } => 40,
+            Text::DailyGivingUp { .. } => 41,
+            Text::DailyStoppedEarly => 42,
+            Text::DailyStoppedHidden => 43,
+            Text::DailyStartedElsewhere => 44,
+            Text::TournamentIntro { .. } => 45,
+            Text::TournamentRules { .. } => 46,
+            Text::TournamentRound { .. } => 47,
+            Text::PlayerPrompt { .. } => 48,
+            Text::TournamentStopped => 49,
+            Text::Forfeits { .. } => 50,
+            Text::PlayerFound { .. } => 51,
+            Text::PlayerOut { .. } => 52,
+            Text::RoundOver { .. } => 53,
+            Text::PlayerResult { .. } => 54,
+            Text::RoundWinners(_) => 55,
+            Text::FinalStandings => 56,
+            Text::Event(_) => 57,
+        }
+    }
+
+    fn parse_errors() -> Vec<ParseError> {
+        vec![
+            ParseError::Empty,
+            ParseError::UnknownToken {
+                token: String::from("x"),
+                column: 1,
+            },
+            ParseError::Unexpected {
+                token: String::from("+"),
+                column: 3,
+            },
+            ParseError::UnexpectedEnd,
+            ParseError::DivisionByZero { column: 2 },
+            ParseError::NotWhole { column: 2 },
+            ParseError::Overflow,
+            ParseError::TooDeep { column: 101 },
+        ]
+    }
+
+    fn events() -> Vec<Event> {
+        let name = || String::from("ann");
+        let mut events = vec![
+            Event::Welcome {
+                min: 1,
+                max: 100,
+                max_attempts: Some(7),
+            },
+            Event::Welcome {
+                min: 1,
+                max: 100,
+                max_attempts: None,
+            },
+            Event::Joined { name: name() },
+            Event::Out { name: name() },
+            Event::Left { name: name() },
+            Event::Winner {
+                name: name(),
+                attempts: 3,
+                secret: 42,
+            },
+            Event::Over { secret: 42 },
+            Event::Error {
+                message: String::from("full"),
+            },
+        ];
+        for ordering in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+            events.push(Event::Guessed {
+                name: name(),
+                guess: 42,
+                ordering,
+            });
+        }
+        events
+    }
+
+    /// At least one of every key, both sides of every `Option` and every case of the enums inside.
+    fn every_text<'a>(
+        errors: &'a [ParseError],
+        reports: &'a [GuessReport],
+        players: &'a [String],
+        events: &'a [Event],
+    ) -> Vec<Text<'a>> {
+        let mut texts = vec![
+            Text::GuessTheNumber,
+            Text::Range {
+                difficulty: Difficulty::Medium,
+                min: 1,
+                max: 100,
+            },
+            Text::Commitment("abc"),
+            Text::CommitmentNote,
+            Text::Prompt {
+                attempts_left: Some(3),
+            },
+            Text::Prompt {
+                attempts_left: None,
+            },
+            Text::TooSmall,
+            Text::TooBig,
+            Text::YouWin,
+            Text::Won { attempts: 4 },
+            Text::Lost { secret: 42 },
+            Text::TimedWon {
+                attempts: 3,
+                seconds: 4.5,
+                score: 700,
+            },
+            Text::TimeUp { secret: 42 },
+            Text::NoMoreInput {
+                attempts: 2,
+                secret: 42,
+            },
+            Text::Reveal {
+                secret: 42,
+                salt: "salt",
+                commitment: "abc",
+            },
+            Text::Resuming,
+            Text::SavePrompt,
+            Text::Abandoned { secret: 42 },
+            Text::Saved { path: "/tmp/x" },
+            Text::Bye,
+            Text::ScreenHelp,
+            Text::Attempts {
+                attempts: 2,
+                max: Some(5),
+            },
+            Text::Attempts {
+                attempts: 2,
+                max: None,
+            },
+            Text::Time { seconds: 75 },
+            Text::StillPossible {
+                low: 1,
+                high: 9,
+                count: 9,
+            },
+            Text::History,
+            Text::PressAnyKey,
+            Text::TimedRound {
+                round: 1,
+                rounds: 3,
+            },
+            Text::TimedPrompt { seconds_left: 12 },
+            Text::SessionStopped,
+            Text::EvilIntro,
+            Text::EvilRange { min: 1, max: 100 },
+            Text::EvilPrompt { possible: 40 },
+            Text::EvilBye,
+            Text::EvilOptimal { attempts: 7 },
+            Text::DailyIntro {
+                date: "2024-02-29".parse().unwrap(),
+                min: 1,
+                max: 100,
+            },
+            Text::DailyNotToday,
+            Text::DailyGivingUp { secret: 42 },
+            Text::DailyStoppedEarly,
+            Text::DailyStoppedHidden,
+            Text::DailyStartedElsewhere,
+            Text::TournamentRules {
+                min: 1,
+                max: 100,
+                attempts: 7,
+            },
+            Text::TournamentRound { round: 2 },
+            Text::PlayerPrompt {
+                player: "ann",
+                attempts_left: Some(3),
+            },
+            Text::PlayerPrompt {
+                player: "ann",
+                attempts_left: None,
+            },
+            Text::TournamentStopped,
+            Text::Forfeits { player: "ann" },
+            Text::PlayerFound {
+                player: "ann",
+                attempts: 4,
+            },
+            Text::PlayerOut { player: "ann" },
+            Text::RoundOver { round: 2 },
+            Text::FinalStandings,
+        ];
+        for shared in [false, true] {
+            texts.push(Text::TimedIntro {
+                rounds: 3,
+                seconds: 30.0,
+                per_session: shared,
+                min: 1,
+                max: 100,
+            });
+            texts.push(Text::TournamentIntro {
+                rounds: 3,
+                players,
+                shared,
+            });
+        }
+        for result in [
+            DailyResult::Playing,
+            DailyResult::Won { attempts: 5 },
+            DailyResult::Lost,
+        ] {
+            texts.push(Text::DailyAlreadyPlayed {
+                name: "ann",
+                result,
+            });
+        }
+        for status in [
+            Status::Playing,
+            Status::Found { attempts: 4 },
+            Status::OutOfAttempts,
+            Status::Forfeited,
+        ] {
+            texts.push(Text::PlayerResult {
+                player: "ann",
+                status,
+                secret: 42,
+            });
+        }
+        for winners in [&players[..0], &players[..1], players] {
+            texts.push(Text::RoundWinners(winners));
+        }
+        texts.extend(events.iter().map(Text::Event));
+        for ordering in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+            texts.push(Text::PastGuess {
+                attempt: 1,
+                guess: 5,
+                ordering,
+            });
+        }
+        for error in errors {
+            texts.push(Text::Invalid(error));
+            texts.push(Text::ParseError(error));
+        }
+        texts.extend(reports.iter().map(Text::Coach));
+        texts
+    }
+
+    #[test]
+    fn every_catalog_has_every_key() {
+        let errors = parse_errors();
+        // Every rating, and one guess that leaves a single number
+        let config = Config::custom(1, 100).unwrap();
+        let reports: Vec<GuessReport> = [
+            (50, Ordering::Less),
+            (60, Ordering::Less),
+            (63, Ordering::Less),
+            (0, Ordering::Less),
+            (90, Ordering::Greater),
+            (70, Ordering::Less),
+            (72, Ordering::Greater),
+            (71, Ordering::Equal),
+        ]
+        .into_iter()
+        .scan(Coach::new(&config), |coach, (guess, ordering)| {
+            Some(coach.observe(guess, ordering))
+        })
+        .collect();
+        let ratings: Vec<Rating> = reports.iter().map(GuessReport::rating).collect();
+        for rating in [
+            Rating::Excellent,
+            Rating::Good,
+            Rating::Fair,
+            Rating::Poor,
+            Rating::Wasted,
+        ] {
+            assert!(
+                ratings.contains(&rating),
+                "no {rating} guess in {ratings:?}"
+            );
+        }
+        let players = [String::from("ann"), String::from("bob")];
+        let events = events();
+        let texts = every_text(&errors, &reports, &players, &events);
+
+        let mut seen = [false; VARIANTS];
+        for text in &texts {
+            seen[variant(text)] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen), "a key has no sample");
+
+        for text in texts {
+            let english = Lang::English.text(text);
+            assert!(!english.trim().is_empty(), "{text:?}");
+            for lang in [Lang::German, Lang::Spanish] {
+                let translated = lang.text(text);
+                assert!(!translated.trim().is_empty(), "{lang} {text:?}");
+                assert_ne!(translated, english, "{lang} {text:?} is not translated");
+            }
+        }
+    }
+
+    #[test]
+    fn languages_come_from_codes_and_locales() {
+        assert_eq!("de_DE.UTF-8".parse(), Ok(Lang::German));
+        assert_eq!("es".parse(), Ok(Lang::Spanish));
+        assert_eq!("C".parse(), Ok(Lang::English));
+        assert!("fr_FR".parse::<Lang>().is_err());
+        assert!(Lang::German.is_yes("Ja"));
+        assert!(Lang::Spanish.is_yes("sí"));
+        assert!(!Lang::English.is_yes("ja"));
+    }
+}
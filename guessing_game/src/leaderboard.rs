@@ -0,0 +1,136 @@
+/*
+ * Leaderboard of won rounds, one tab separated line per entry.
+ * Writers take an exclusive lock on the file and append the whole line in one write,
+ * so two terminals finishing at the same time cannot interleave their entries.
+ */
+
+use crate::config::Difficulty;
+use std::{
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub name: String,
+    pub attempts: u32,
+    pub min: i128,
+    pub max: i128,
+    pub difficulty: Difficulty,
+    pub elapsed: Duration,
+}
+
+impl Entry {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\n",
+            clean_name(&self.name),
+            self.attempts,
+            self.min,
+            self.max,
+            self.difficulty,
+            self.elapsed.as_millis()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let entry = Self {
+            name: fields.next()?.to_string(),
+            attempts: fields.next()?.parse().ok()?,
+            min: fields.next()?.parse().ok()?,
+            max: fields.next()?.parse().ok()?,
+            difficulty: fields.next()?.parse().ok()?,
+            elapsed: Duration::from_millis(fields.next()?.parse().ok()?),
+        };
+
+        fields.next().is_none().then_some(entry)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Leaderboard {
+    path: PathBuf,
+}
+
+impl Leaderboard {
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `$XDG_DATA_HOME/guessing_game/leaderboard.tsv`, falling back to `~/.local/share`.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(data_dir()?.join("leaderboard.tsv"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, entry: &Entry) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock()?;
+        file.write_all(entry.to_line().as_bytes())?;
+        file.flush()
+        // The lock is released when the file is dropped
+    }
+
+    /// Every readable entry, lines that fail to parse are skipped.
+    pub fn entries(&self) -> io::Result<Vec<Entry>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        file.lock_shared()?;
+
+        let mut entries = Vec::new();
+        for line in BufReader::new(&file).lines() {
+            if let Some(entry) = Entry::from_line(&line?) {
+                entries.push(entry);
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Best `limit` entries for one difficulty, fewest attempts first and then fastest.
+    pub fn top(&self, difficulty: Difficulty, limit: usize) -> io::Result<Vec<Entry>> {
+        let mut entries: Vec<Entry> = self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.difficulty == difficulty)
+            .collect();
+
+        entries.sort_by_key(|entry| (entry.attempts, entry.elapsed));
+        entries.truncate(limit);
+        Ok(entries)
+    }
+}
+
+/// Tabs and newlines would break the files, names are the only free text in them.
+pub(crate) fn clean_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect()
+}
+
+/// Directory for everything the game keeps between runs.
+pub fn data_dir() -> Option<PathBuf> {
+    let base = match env::var_os("XDG_DATA_HOME").filter(|dir| Path::new(dir).is_absolute()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".local/share"),
+    };
+
+    Some(base.join("guessing_game"))
+}
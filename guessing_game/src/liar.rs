@@ -0,0 +1,482 @@
+/*
+ * Ulam's liar game. The player only asks "is it higher than x?" and the host may lie
+ * up to k times per round, so a single answer proves nothing. The player names the
+ * number once sure.
+ *
+ * Every number is tracked with how many answers it would make a lie, those needing
+ * more than k are out. Berlekamp's volume counts how many ways the remaining answers
+ * could still go: with q questions left a number that already cost l lies weighs
+ * C(q,0) + ... + C(q,k-l). Every question splits the volume in two, so q questions
+ * can only finish the job while the volume is at most 2^q. The solver asks whatever
+ * splits the volume most evenly.
+ */
+
+use crate::{config::Config, strategy::Interval};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+/// How often the random liar uses up one of its lies.
+const LIE_CHANCE: f64 = 0.25;
+
+/// Answer to "is it higher than x?".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    Higher,
+    NotHigher,
+}
+
+impl Reply {
+    /// The honest reply about `secret`.
+    pub fn truth(secret: i128, threshold: i128) -> Self {
+        if secret > threshold {
+            Self::Higher
+        } else {
+            Self::NotHigher
+        }
+    }
+
+    pub fn flipped(self) -> Self {
+        match self {
+            Self::Higher => Self::NotHigher,
+            Self::NotHigher => Self::Higher,
+        }
+    }
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Higher => "Higher",
+            Self::NotHigher => "Not higher",
+        })
+    }
+}
+
+/// Numbers from `low` to `high` that would all make `lies` of the answers so far lies.
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    low: i128,
+    high: i128,
+    lies: u32,
+}
+
+impl Segment {
+    fn len(&self) -> u128 {
+        Interval::new(self.low, self.high).len()
+    }
+}
+
+/// The numbers that are still possible, kept as ranges so huge ranges stay cheap.
+#[derive(Debug, Clone)]
+pub struct Candidates {
+    segments: Vec<Segment>,
+    max_lies: u32,
+}
+
+impl Candidates {
+    pub fn new(config: &Config, max_lies: u32) -> Self {
+        Self {
+            segments: vec![Segment {
+                low: config.min(),
+                high: config.max(),
+                lies: 0,
+            }],
+            max_lies,
+        }
+    }
+
+    pub fn max_lies(&self) -> u32 {
+        self.max_lies
+    }
+
+    /// Amount of numbers that are still possible.
+    pub fn alive(&self) -> u128 {
+        self.segments
+            .iter()
+            .fold(0u128, |total, segment| total.saturating_add(segment.len()))
+    }
+
+    /// The number, once it is the only one left.
+    pub fn only(&self) -> Option<i128> {
+        match self.segments.as_slice() {
+            [segment] if segment.low == segment.high => Some(segment.low),
+            _ => None,
+        }
+    }
+
+    /// How many of the answers so far would be lies if `number` was the secret.
+    pub fn lies(&self, number: i128) -> Option<u32> {
+        self.segments
+            .iter()
+            .find(|segment| (segment.low..=segment.high).contains(&number))
+            .map(|segment| segment.lies)
+    }
+
+    /// Smallest and biggest number still possible.
+    pub fn bounds(&self) -> Option<(i128, i128)> {
+        Some((self.segments.first()?.low, self.segments.last()?.high))
+    }
+
+    pub fn record(&mut self, threshold: i128, reply: Reply) {
+        let mut segments: Vec<Segment> = Vec::with_capacity(self.segments.len() + 1);
+
+        for segment in &self.segments {
+            // The part at or below the threshold, then the part above it
+            let parts = [
+                (segment.low, segment.high.min(threshold), Reply::NotHigher),
+                (
+                    segment.low.max(threshold.saturating_add(1)),
+                    segment.high,
+                    Reply::Higher,
+                ),
+            ];
+            for (low, high, honest) in parts {
+                if low > high || (threshold == i128::MAX && honest == Reply::Higher) {
+                    continue;
+                }
+                let lies = segment.lies + u32::from(honest != reply);
+                if lies > self.max_lies {
+                    continue;
+                }
+                match segments.last_mut() {
+                    Some(last) if last.lies == lies && last.high.checked_add(1) == Some(low) => {
+                        last.high = high
+                    }
+                    _ => segments.push(Segment { low, high, lies }),
+                }
+            }
+        }
+
+        self.segments = segments;
+    }
+
+    /// Berlekamp's volume with `questions` left to ask.
+    pub fn volume(&self, questions: u32) -> f64 {
+        self.segments
+            .iter()
+            .map(|segment| segment.len() as f64 * weight(segment.lies, questions, self.max_lies))
+            .sum()
+    }
+
+    /// Questions needed at the very least before the number is certain.
+    pub fn questions_needed(&self) -> u32 {
+        needed(|questions| self.volume(questions))
+    }
+
+    /// The volumes after "higher" and after "not higher" to `threshold`.
+    fn split(&self, threshold: i128, questions: u32) -> (f64, f64) {
+        let mut higher = 0.0;
+        let mut not_higher = 0.0;
+
+        for segment in &self.segments {
+            let weight_of = |lies| weight(lies, questions, self.max_lies);
+            let below = Interval::new(segment.low, segment.high.min(threshold)).len() as f64;
+            let above = segment.len() as f64 - below;
+
+            higher += below * weight_of(segment.lies + 1) + above * weight_of(segment.lies);
+            not_higher += below * weight_of(segment.lies) + above * weight_of(segment.lies + 1);
+        }
+
+        (higher, not_higher)
+    }
+}
+
+/// C(q,0) + ... + C(q,k-l), the ways `questions` answers can go with the lies left.
+fn weight(lies: u32, questions: u32, max_lies: u32) -> f64 {
+    if lies > max_lies {
+        return 0.0;
+    }
+
+    let mut binomial = 1.0;
+    let mut total = 1.0;
+    for j in 1..=(max_lies - lies).min(questions) {
+        binomial = binomial * f64::from(questions - j + 1) / f64::from(j);
+        total += binomial;
+    }
+    total
+}
+
+fn needed(volume: impl Fn(u32) -> f64) -> u32 {
+    (0..)
+        .find(|&questions| volume(questions) <= 2f64.powi(questions as i32))
+        .expect("the volume only grows polynomially")
+}
+
+/// The volume bound for a fresh round, no strategy can be sure with fewer questions.
+pub fn questions_needed(range_size: u128, max_lies: u32) -> u32 {
+    needed(|questions| range_size as f64 * weight(0, questions, max_lies))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Ask(i128),
+    Claim(i128),
+}
+
+/// Asks the question that splits the volume most evenly, claims once one number is left.
+#[derive(Debug, Clone)]
+pub struct Solver {
+    candidates: Candidates,
+}
+
+impl Solver {
+    pub fn new(config: &Config, max_lies: u32) -> Self {
+        Self {
+            candidates: Candidates::new(config, max_lies),
+        }
+    }
+
+    pub fn candidates(&self) -> &Candidates {
+        &self.candidates
+    }
+
+    /// `None` once the host lied more often than allowed and no number fits.
+    pub fn next_move(&self) -> Option<Move> {
+        if let Some(number) = self.candidates.only() {
+            return Some(Move::Claim(number));
+        }
+        let (low, high) = self.candidates.bounds()?;
+        let questions = self.candidates.questions_needed().saturating_sub(1);
+        let worse = |threshold| {
+            let (higher, not_higher) = self.candidates.split(threshold, questions);
+            higher.max(not_higher)
+        };
+
+        // "Higher" gets less likely as the threshold grows and "not higher" more likely,
+        // find where they cross. Thresholds stay below `high` so every question splits.
+        let mut range = Interval::new(low, high - 1);
+        while range.low < range.high {
+            let threshold = range.midpoint();
+            let (higher, not_higher) = self.candidates.split(threshold, questions);
+            if not_higher >= higher {
+                range.high = threshold;
+            } else {
+                range.low = threshold + 1;
+            }
+        }
+
+        let threshold = if range.low > low && worse(range.low - 1) < worse(range.low) {
+            range.low - 1
+        } else {
+            range.low
+        };
+        Some(Move::Ask(threshold))
+    }
+
+    pub fn feedback(&mut self, threshold: i128, reply: Reply) {
+        self.candidates.record(threshold, reply);
+    }
+}
+
+#[derive(Debug)]
+enum Liar {
+    /// Picks the secret up front and lies now and then.
+    Random { secret: i128, rng: Box<ChaCha8Rng> },
+    /// Like the evil host, only commits when forced and spends its lies where they hurt most.
+    Adversary,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right { questions: u32 },
+    Wrong { secret: i128 },
+}
+
+#[derive(Debug)]
+pub struct LiarGame {
+    config: Config,
+    liar: Liar,
+    candidates: Candidates,
+    questions: Vec<(i128, Reply)>,
+    secret: Option<i128>,
+}
+
+impl LiarGame {
+    pub fn new(config: Config, max_lies: u32) -> Self {
+        Self::with_rng(config, max_lies, ChaCha8Rng::from_entropy())
+    }
+
+    pub fn with_seed(config: Config, max_lies: u32, seed: u64) -> Self {
+        Self::with_rng(config, max_lies, ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    fn with_rng(config: Config, max_lies: u32, mut rng: ChaCha8Rng) -> Self {
+        let secret = rng.gen_range(config.min()..=config.max());
+        Self {
+            liar: Liar::Random {
+                secret,
+                rng: Box::new(rng),
+            },
+            ..Self::adversary(config, max_lies)
+        }
+    }
+
+    pub fn adversary(config: Config, max_lies: u32) -> Self {
+        Self {
+            candidates: Candidates::new(&config, max_lies),
+            config,
+            liar: Liar::Adversary,
+            questions: Vec::new(),
+            secret: None,
+        }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn max_lies(&self) -> u32 {
+        self.candidates.max_lies()
+    }
+
+    pub fn questions(&self) -> &[(i128, Reply)] {
+        &self.questions
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.secret.is_some()
+    }
+
+    /// "Is it higher than `threshold`?", `None` once the round is over.
+    pub fn ask(&mut self, threshold: i128) -> Option<Reply> {
+        if self.is_over() {
+            return None;
+        }
+
+        let reply = match &mut self.liar {
+            Liar::Random { secret, rng } => {
+                let truth = Reply::truth(*secret, threshold);
+                let lies_left = self.candidates.lies(*secret) < Some(self.candidates.max_lies());
+                if lies_left && rng.gen_bool(LIE_CHANCE) {
+                    truth.flipped()
+                } else {
+                    truth
+                }
+            }
+            Liar::Adversary => {
+                // Whichever reply leaves the player further from certain
+                let outlook = |reply| {
+                    let mut candidates = self.candidates.clone();
+                    candidates.record(threshold, reply);
+                    let needed = candidates.questions_needed();
+                    (needed, candidates.volume(needed))
+                };
+                let (higher, not_higher) = (outlook(Reply::Higher), outlook(Reply::NotHigher));
+                if higher.0 > not_higher.0 || (higher.0 == not_higher.0 && higher.1 >= not_higher.1)
+                {
+                    Reply::Higher
+                } else {
+                    Reply::NotHigher
+                }
+            }
+        };
+
+        self.candidates.record(threshold, reply);
+        self.questions.push((threshold, reply));
+        Some(reply)
+    }
+
+    /// Names the secret, this ends the round either way.
+    pub fn claim(&mut self, number: i128) -> Verdict {
+        let secret = match (&self.liar, self.secret) {
+            (_, Some(secret)) => secret,
+            (Liar::Random { secret, .. }, None) => *secret,
+            // Any other number that still fits beats the player, it has to be honest otherwise
+            (Liar::Adversary, None) => self
+                .candidates
+                .segments
+                .iter()
+                .flat_map(|segment| [segment.low, segment.high])
+                .find(|&candidate| candidate != number)
+                .unwrap_or(number),
+        };
+        self.secret = Some(secret);
+
+        if secret == number {
+            Verdict::Right {
+                questions: self.questions.len() as u32,
+            }
+        } else {
+            Verdict::Wrong { secret }
+        }
+    }
+
+    /// The questions, counting from 1, that were answered with a lie. Known once the round is over.
+    pub fn lies(&self) -> Option<Vec<usize>> {
+        let secret = self.secret?;
+        Some(
+            self.questions
+                .iter()
+                .enumerate()
+                .filter(|(_, (threshold, reply))| Reply::truth(secret, *threshold) != *reply)
+                .map(|(index, _)| index + 1)
+                .collect(),
+        )
+    }
+}
+
+/// Plays a whole round, `None` if the host broke the rules and no number fits.
+pub fn play_round(solver: &mut Solver, game: &mut LiarGame) -> Option<Verdict> {
+    loop {
+        match solver.next_move()? {
+            Move::Ask(threshold) => {
+                let reply = game.ask(threshold)?;
+                solver.feedback(threshold, reply);
+            }
+            Move::Claim(number) => return Some(game.claim(number)),
+        }
+    }
+}
+
+/// Questions needed with `lies` lies, by the volume bound and by the solver against the adversary.
+#[derive(Debug, Clone, Copy)]
+pub struct LieCost {
+    pub lies: u32,
+    pub bound: u32,
+    pub solver: u32,
+}
+
+/// One row for every lie count from none up to `max_lies`.
+pub fn lie_costs(config: &Config, max_lies: u32) -> Vec<LieCost> {
+    (0..=max_lies)
+        .map(|lies| {
+            let mut solver = Solver::new(config, lies);
+            let mut game = LiarGame::adversary(*config, lies);
+            play_round(&mut solver, &mut game);
+
+            LieCost {
+                lies,
+                bound: questions_needed(config.range_size(), lies),
+                solver: game.questions().len() as u32,
+            }
+        })
+        .collect()
+}
+
+pub struct CostTable<'a>(pub &'a [LieCost]);
+
+impl fmt::Display for CostTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:>6}  {:>6}  {:>14}",
+            "Lies", "Bound", "Solver", "Extra per lie"
+        )?;
+
+        let mut previous: Option<&LieCost> = None;
+        for cost in self.0 {
+            let extra = previous.map_or_else(
+                || String::from("-"),
+                |previous| format!("+{}", cost.solver.saturating_sub(previous.solver)),
+            );
+            writeln!(
+                f,
+                "{:>4}  {:>6}  {:>6}  {:>14}",
+                cost.lies, cost.bound, cost.solver, extra
+            )?;
+            previous = Some(cost);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+/*
+ * Guessing Game engine
+ * The binary in main.rs is only a thin loop around this, so other tools can embed the game too.
+ */
+
+pub mod bulls;
+pub mod coach;
+pub mod config;
+pub mod console;
+pub mod daily;
+pub mod evil;
+pub mod fair;
+pub mod game;
+pub mod http;
+pub mod i18n;
+pub mod leaderboard;
+pub mod liar;
+pub mod net;
+pub mod parse;
+pub mod protocol;
+pub mod reverse;
+pub mod session;
+pub mod simulate;
+pub mod stats;
+pub mod strategy;
+pub mod timed;
+pub mod tournament;
+pub mod tui;
+
+pub use config::{Config, ConfigError, Difficulty};
+pub use game::{Game, Outcome};
+pub use leaderboard::{Entry, Leaderboard};
+pub use simulate::{simulate, simulate_against, Report};
+pub use strategy::Strategy;
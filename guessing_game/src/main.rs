@@ -1,42 +1,1346 @@
-use rand::Rng;
-use std::{cmp::Ordering, io};
+use guessing_game::{
+    bulls::{BullsAndCows, CodeConfig, Solver},
+    coach::{Coach, CoachReport},
+    console::{Console, Input},
+    daily::{self, DailyLog, DailyResult, Date, Puzzle},
+    evil::EvilHost,
+    fair::{self, Seal},
+    game::Host,
+    http::ApiServer,
+    i18n::{Lang, Text},
+    liar::{self, CostTable, LiarGame, Move, Verdict},
+    net::{Client, Server},
+    parse::parse_guess,
+    reverse::{Answer, Reverse, Step},
+    session::SavedRound,
+    simulate, simulate_against,
+    stats::{History, PlayedRound, Stats},
+    strategy,
+    timed::{Progress, RoundEnd, ScoreTable, SystemClock, TimeLimit, TimedSession},
+    tournament::{Mode, StandingsTable, Status, Tournament},
+    Config, Difficulty, Entry, Game, Leaderboard, Outcome,
+};
+use std::{
+    env, io, process, thread,
+    time::{Duration, Instant},
+};
+
+/* Command line options, parsed by hand to keep the dependencies small */
+#[derive(Debug, Default)]
+struct Options {
+    seed: Option<u64>,
+    difficulty: Option<Difficulty>,
+    min: Option<i128>,
+    max: Option<i128>,
+    max_attempts: Option<u32>,
+    name: Option<String>,
+    top: Option<usize>,
+    strategy: Option<String>,
+    rounds: Option<u32>,
+    port: Option<u16>,
+    addr: Option<String>,
+    length: Option<usize>,
+    repeats: bool,
+    solve: bool,
+    tui: bool,
+    protocol: Option<String>,
+    resume: bool,
+    time: Option<f64>,
+    per_session: bool,
+    ttl: Option<u64>,
+    compare: bool,
+    lies: Option<u32>,
+    evil: bool,
+    report: bool,
+    date: Option<Date>,
+    players: Option<String>,
+    shared: bool,
+    coach: bool,
+    lang: Option<Lang>,
+}
 
 fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("scores") => scores(&args[1..]),
+        Some("stats") => stats(&args[1..]),
+        Some("simulate") => simulate_rounds(&args[1..]),
+        Some("serve") => serve(&args[1..]),
+        Some("join") => join(&args[1..]),
+        Some("reverse") => reverse(&args[1..]),
+        Some("bulls") => bulls(&args[1..]),
+        Some("timed") => timed(&args[1..]),
+        Some("http") => http(&args[1..]),
+        Some("evil") => evil(&args[1..]),
+        Some("liar") => liar_game(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("daily") => daily_challenge(&args[1..]),
+        Some("tournament") => tournament(&args[1..]),
+        _ => play(&args),
+    }
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(2);
+}
+
+fn play(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+
+    match options.protocol.as_deref() {
+        None | Some("text") => {}
+        Some("jsonl") => return play_jsonl(&options, config),
+        Some(other) => fail(&format!(
+            "Unknown protocol `{other}`, expected text or jsonl"
+        )),
+    }
+
+    let lang = options.lang.unwrap_or_else(Lang::from_env);
+    if options.tui {
+        return play_tui(&options, config, lang);
+    }
+
+    // The secret is picked once per round, not on every guess
+    let (mut game, seal, name, elapsed_before) = if options.resume {
+        resume_round(&options, lang)
+    } else {
+        let game = match options.seed {
+            Some(seed) => Game::with_seed(config, seed),
+            None => Game::new(config),
+        };
+        let seal = Seal::new(game.secret());
+        (game, seal, options.name.clone(), Duration::ZERO)
+    };
+    let config = *game.config();
+
+    println!("{}", lang.text(Text::GuessTheNumber));
+    println!(
+        "{}",
+        lang.text(Text::Range {
+            difficulty: config.difficulty(),
+            min: config.min(),
+            max: config.max(),
+        })
+    );
+
+    println!("{}", lang.text(Text::Commitment(&seal.commitment())));
+    println!("{}", lang.text(Text::CommitmentNote));
+
+    for (attempt, (guess, ordering)) in game.history().enumerate() {
+        println!(
+            "{}",
+            lang.text(Text::PastGuess {
+                attempt: attempt + 1,
+                guess,
+                ordering,
+            })
+        );
+    }
+
+    let mut coach = options.coach.then(|| {
+        let mut coach = Coach::new(&config);
+        for (guess, ordering) in game.history() {
+            coach.observe(guess, ordering);
+        }
+        coach
+    });
+
+    let console = Console::new();
+    let started = Instant::now();
+    let elapsed = || elapsed_before + started.elapsed();
+
     loop {
-        println!("Guess the number!");
+        println!(
+            "{}",
+            lang.text(Text::Prompt {
+                attempts_left: game.attempts_left(),
+            })
+        );
+
+        let guess = match console.read() {
+            Input::Line(line) => line,
+            Input::Eof => {
+                println!(
+                    "{}",
+                    lang.text(Text::NoMoreInput {
+                        attempts: game.attempts(),
+                        secret: game.secret(),
+                    })
+                );
+                if let Some(coach) = &coach {
+                    print!("{}", CoachReport(coach.reports()));
+                }
+                return reveal(&seal, lang);
+            }
+            Input::Interrupt => {
+                return offer_save(&console, &game, &seal, name, elapsed(), lang);
+            }
+        };
+
+        let guess = match parse_guess(&guess) {
+            Ok(guess) => guess,
+            Err(e) => {
+                println!("{}", lang.text(Text::Invalid(&e)));
+                continue;
+            }
+        };
+
+        match game.check(guess) {
+            // Only after the round is over, and the loop stops before that
+            Outcome::Invalid => continue,
+            Outcome::TooSmall => println!("{}", lang.text(Text::TooSmall)),
+            Outcome::TooBig => println!("{}", lang.text(Text::TooBig)),
+            Outcome::Won { attempts } => {
+                println!("{}", lang.text(Text::Won { attempts }));
+                record_win(name.as_deref(), &config, attempts, elapsed());
+                record_round(name.as_deref(), &config, attempts, true, elapsed());
+            }
+            Outcome::Lost { secret } => {
+                println!("{}", lang.text(Text::Lost { secret }));
+                record_round(name.as_deref(), &config, game.attempts(), false, elapsed());
+            }
+        }
+
+        if let Some(coach) = coach.as_mut() {
+            let report = coach.observe(guess, game.compare(guess));
+            println!("  {}", lang.text(Text::Coach(&report)));
+        }
+        if game.is_over() {
+            break;
+        }
+    }
+
+    if let Some(coach) = &coach {
+        print!("{}", CoachReport(coach.reports()));
+    }
+    reveal(&seal, lang);
+}
+
+fn reveal(seal: &Seal, lang: Lang) {
+    println!(
+        "{}",
+        lang.text(Text::Reveal {
+            secret: seal.secret(),
+            salt: seal.salt(),
+            commitment: &seal.commitment(),
+        })
+    );
+}
+
+fn resume_round(options: &Options, lang: Lang) -> (Game, Seal, Option<String>, Duration) {
+    let path = SavedRound::default_path()
+        .unwrap_or_else(|| fail("Could not find a data directory, set XDG_DATA_HOME or HOME"));
+    let (saved, game) = SavedRound::take(&path)
+        .unwrap_or_else(|e| fail(&format!("Could not resume from {}: {e}", path.display())));
+
+    println!("{}", lang.text(Text::Resuming));
+    let elapsed = saved.elapsed();
+    // Rounds saved before commitments existed get a fresh salt, and so a new commitment
+    let seal = match saved.salt {
+        Some(salt) => Seal::with_salt(game.secret(), salt),
+        None => Seal::new(game.secret()),
+    };
+    (game, seal, options.name.clone().or(saved.name), elapsed)
+}
+
+/* Ctrl-C in the middle of a round, the player may keep it for later */
+fn offer_save(
+    console: &Console,
+    game: &Game,
+    seal: &Seal,
+    name: Option<String>,
+    elapsed: Duration,
+    lang: Lang,
+) {
+    println!();
+    println!("{}", lang.text(Text::SavePrompt));
+
+    let wants_save = matches!(console.read(), Input::Line(answer) if lang.is_yes(&answer));
+    if !wants_save {
+        println!(
+            "{}",
+            lang.text(Text::Abandoned {
+                secret: game.secret()
+            })
+        );
+        return reveal(seal, lang);
+    }
+
+    let saved = SavedRound::from_game(game, seal.salt(), name, elapsed);
+    let result = SavedRound::default_path()
+        .ok_or_else(|| io::Error::other("no data directory, set XDG_DATA_HOME or HOME"))
+        .and_then(|path| saved.save(&path).map(|_| path));
+
+    match result {
+        Ok(path) => println!(
+            "{}",
+            lang.text(Text::Saved {
+                path: &path.display().to_string(),
+            })
+        ),
+        Err(e) => fail(&format!("Could not save the round: {e}")),
+    }
+}
 
-        let secret_number = rand::thread_rng().gen_range(1..=100);
+fn play_jsonl(options: &Options, config: Config) {
+    let mut game = match options.seed {
+        Some(seed) => Game::with_seed(config, seed),
+        None => Game::new(config),
+    };
 
-        println!("The secret number is: {secret_number}");
+    let seal = Seal::new(game.secret());
 
-        println!("Please input your guess!");
+    // Bots get no leaderboard entry, their rounds would crowd out the people
+    if let Err(e) = guessing_game::protocol::run(&mut game, &seal, io::stdin().lock(), io::stdout())
+    {
+        fail(&format!("The protocol stream failed: {e}"));
+    }
+}
 
-        let mut guess = String::new(); // mutable value, defaults are immutables
+fn play_tui(options: &Options, config: Config, lang: Lang) {
+    let mut game = match options.seed {
+        Some(seed) => Game::with_seed(config, seed),
+        None => Game::new(config),
+    };
+    let seal = Seal::new(game.secret());
+    let started = Instant::now();
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read the line!");
+    match guessing_game::tui::run(&mut game, &seal.commitment(), lang) {
+        Ok(Some(Outcome::Won { attempts })) => {
+            println!("{}", lang.text(Text::Won { attempts }));
+            record_win(
+                options.name.as_deref(),
+                &config,
+                attempts,
+                started.elapsed(),
+            );
+            record_round(
+                options.name.as_deref(),
+                &config,
+                attempts,
+                true,
+                started.elapsed(),
+            );
+        }
+        Ok(Some(Outcome::Lost { secret })) => {
+            println!("{}", lang.text(Text::Lost { secret }));
+            record_round(
+                options.name.as_deref(),
+                &config,
+                game.attempts(),
+                false,
+                started.elapsed(),
+            );
+        }
+        Ok(_) => println!("{}", lang.text(Text::Bye)),
+        Err(e) => fail(&format!("The terminal UI failed: {e}")),
+    }
+    reveal(&seal, lang);
+}
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                println!("Aah! Looks like you put some invalid input. Try again!");
-                continue
+/* Keeps reading until `parse` accepts the line, printing its message each time it does not.
+ * `None` when the input ended or the player pressed Ctrl-C. */
+fn read_valid<T>(console: &Console, mut parse: impl FnMut(&str) -> Result<T, String>) -> Option<T> {
+    loop {
+        let Input::Line(line) = console.read() else {
+            return None;
+        };
+
+        match parse(&line) {
+            Ok(value) => return Some(value),
+            Err(message) => println!("{message}"),
+        }
+    }
+}
+
+fn record_win(name: Option<&str>, config: &Config, attempts: u32, elapsed: Duration) {
+    let Some(path) = Leaderboard::default_path() else {
+        return;
+    };
+
+    let entry = Entry {
+        name: name.map_or_else(default_player_name, String::from),
+        attempts,
+        min: config.min(),
+        max: config.max(),
+        difficulty: config.difficulty(),
+        elapsed,
+    };
+
+    // A broken leaderboard should never spoil a won round
+    if let Err(e) = Leaderboard::open(path).record(&entry) {
+        eprintln!("Could not save your score: {e}");
+    }
+}
+
+/// Every finished round goes into the player's history, won or lost.
+fn record_round(name: Option<&str>, config: &Config, attempts: u32, won: bool, elapsed: Duration) {
+    let Some(path) = History::default_path() else {
+        return;
+    };
+
+    let name = name.map_or_else(default_player_name, String::from);
+    let round = PlayedRound::now(&name, config, attempts, won, elapsed);
+    if let Err(e) = History::open(path).record(&round) {
+        eprintln!("Could not save the round to your history: {e}");
+    }
+}
+
+fn player_name(options: &Options) -> String {
+    options.name.clone().unwrap_or_else(default_player_name)
+}
+
+fn default_player_name() -> String {
+    env::var("USER").unwrap_or_else(|_| String::from("player"))
+}
+
+fn scores(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let path = Leaderboard::default_path()
+        .unwrap_or_else(|| fail("Could not find a data directory, set XDG_DATA_HOME or HOME"));
+    let leaderboard = Leaderboard::open(path);
+
+    let difficulties = match options.difficulty {
+        Some(difficulty) => vec![difficulty],
+        None => vec![
+            Difficulty::Easy,
+            Difficulty::Medium,
+            Difficulty::Hard,
+            Difficulty::Custom,
+        ],
+    };
+
+    for difficulty in difficulties {
+        let entries = leaderboard
+            .top(difficulty, options.top.unwrap_or(10))
+            .unwrap_or_else(|e| fail(&format!("Could not read the leaderboard: {e}")));
+
+        println!("== {difficulty} ==");
+        if entries.is_empty() {
+            println!("  no rounds yet");
+        }
+        for (rank, entry) in entries.iter().enumerate() {
+            println!(
+                "{:>3}. {:<16} {:>4} attempts {:>8.1}s  {}..={}",
+                rank + 1,
+                entry.name,
+                entry.attempts,
+                entry.elapsed.as_secs_f64(),
+                entry.min,
+                entry.max
+            );
+        }
+    }
+}
+
+fn stats(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let path = History::default_path()
+        .unwrap_or_else(|| fail("Could not find a data directory, set XDG_DATA_HOME or HOME"));
+    let name = player_name(&options);
+
+    let rounds = History::open(path)
+        .rounds(&name)
+        .unwrap_or_else(|e| fail(&format!("Could not read the history: {e}")));
+    print!("{}", Stats::new(&name, &rounds));
+}
+
+fn simulate_rounds(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+    let seed = options.seed.unwrap_or_else(rand::random);
+
+    let name = options.strategy.as_deref().unwrap_or("binary");
+    // The bots must not share the game's random stream, or they would draw the secret itself
+    let strategy_seed = seed ^ 0x9E37_79B9_7F4A_7C15;
+    let mut strategy = strategy::by_name(name, strategy_seed).unwrap_or_else(|| {
+        fail(&format!(
+            "Unknown strategy `{name}`, expected one of {}",
+            strategy::STRATEGY_NAMES.join(", ")
+        ))
+    });
+
+    let report = simulate(
+        strategy.as_mut(),
+        config,
+        options.rounds.unwrap_or(1000),
+        seed,
+    );
+    print!("{report}");
+}
+
+fn serve(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+
+    let server = Server::bind(
+        ("0.0.0.0", options.port.unwrap_or(7878)),
+        config,
+        options.seed,
+    )
+    .unwrap_or_else(|e| fail(&format!("Could not start the server: {e}")));
+    let addr = server
+        .local_addr()
+        .unwrap_or_else(|e| fail(&format!("Could not start the server: {e}")));
+
+    println!(
+        "Hosting a round on {addr}, range {}..={}",
+        config.min(),
+        config.max()
+    );
+    println!(
+        "Players can join with: guessing_game join --addr <this host>:{} --name <name>",
+        addr.port()
+    );
+
+    let summary = server
+        // The host's log stays in English like the rest of this mode
+        .run(|event| println!("{}", Lang::English.text(Text::Event(event))))
+        .unwrap_or_else(|e| fail(&format!("The server stopped: {e}")));
+
+    if summary.winner.is_none() {
+        println!("Nobody found it, the secret number was {}.", summary.secret);
+    }
+}
+
+fn join(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    let lang = options.lang.unwrap_or_else(Lang::from_env);
+    let addr = options
+        .addr
+        .clone()
+        .unwrap_or_else(|| String::from("127.0.0.1:7878"));
+
+    let mut client = Client::connect(addr.as_str(), &player_name(&options))
+        .unwrap_or_else(|e| fail(&format!("Could not join {addr}: {e}")));
+    let mut guesser = client
+        .guesser()
+        .unwrap_or_else(|e| fail(&format!("Could not join {addr}: {e}")));
+
+    // Guesses are typed while the other players' guesses keep scrolling by
+    thread::spawn(move || loop {
+        let mut guess = String::new();
+        match io::stdin().read_line(&mut guess) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+
+        match parse_guess(&guess) {
+            Ok(guess) => {
+                if guesser.guess(guess).is_err() {
+                    break;
+                }
+            }
+            Err(e) => println!("{}", lang.text(Text::Invalid(&e))),
+        }
+    });
+
+    loop {
+        match client.next_event() {
+            Ok(Some(event)) => println!("{}", lang.text(Text::Event(&event))),
+            Ok(None) => break,
+            Err(e) => fail(&format!("Lost the connection: {e}")),
+        }
+    }
+}
+
+fn reverse(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+
+    println!(
+        "Think of a number between {} and {}, I will guess it!",
+        config.min(),
+        config.max()
+    );
+    println!("Answer higher, lower or correct.");
+
+    let mut reverse = Reverse::new(&config);
+    let console = Console::new();
+
+    loop {
+        println!("Is it {}?", reverse.guess());
+
+        let Some(answer) = read_valid(&console, |line| {
+            line.parse::<Answer>()
+                .map_err(|message| format!("{message} Try again!"))
+        }) else {
+            println!("Bye! I never found out your number.");
+            return;
+        };
+
+        match reverse.answer(answer) {
+            Ok(Step::Guess(_)) => {}
+            Ok(Step::Found { number, attempts }) => {
+                println!("Got it, your number is {number}! It took me {attempts} guesses.");
+                break;
+            }
+            Err(contradiction) => {
+                println!("That can't be right, {contradiction}.");
+                break;
+            }
+        }
+    }
+}
+
+fn bulls(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let code_config = CodeConfig::new(options.length.unwrap_or(4), options.repeats)
+        .unwrap_or_else(|e| fail(&e.to_string()));
+
+    let mut game = match options.seed {
+        Some(seed) => BullsAndCows::with_seed(code_config, seed),
+        None => BullsAndCows::new(code_config),
+    };
+
+    println!(
+        "Guess the {}-digit code! Digits {}.",
+        code_config.length(),
+        if code_config.repeats() {
+            "may repeat"
+        } else {
+            "are all different"
+        }
+    );
+
+    let mut solver = options.solve.then(|| Solver::new(code_config));
+    let console = Console::new();
+
+    loop {
+        let guess = match solver.as_ref() {
+            Some(solver) => {
+                let guess = solver
+                    .next_guess()
+                    .unwrap_or_else(|| fail("The solver ran out of codes to try"));
+                println!("Solver guesses {guess}");
+                guess
+            }
+            None => {
+                println!("Please input your guess!");
+                let guess = read_valid(&console, |line| {
+                    code_config
+                        .parse(line)
+                        .map_err(|e| format!("{e}. Try again!"))
+                });
+                match guess {
+                    Some(guess) => guess,
+                    None => {
+                        println!(
+                            "Round over after {} attempts, the code was {}.",
+                            game.attempts(),
+                            game.secret()
+                        );
+                        return;
+                    }
+                }
+            }
+        };
+
+        let score = game.guess(&guess);
+        if game.is_won(score) {
+            println!(
+                "You Win! The code was {}, found in {} attempts.",
+                game.secret(),
+                game.attempts()
+            );
+            break;
+        }
+
+        println!("{} bulls, {} cows", score.bulls, score.cows);
+        if let Some(solver) = solver.as_mut() {
+            solver.feedback(&guess, score);
+        }
+
+        if options.max_attempts == Some(game.attempts()) {
+            println!("Out of attempts! The code was {}.", game.secret());
+            break;
+        }
+    }
+}
+
+fn timed(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+
+    let lang = options.lang.unwrap_or_else(Lang::from_env);
+    let seconds = options.time.unwrap_or(30.0);
+    let time = Duration::try_from_secs_f64(seconds)
+        .ok()
+        .filter(|time| !time.is_zero())
+        .unwrap_or_else(|| fail(&format!("--time got an invalid value `{seconds}`")));
+    let limit = if options.per_session {
+        TimeLimit::PerSession(time)
+    } else {
+        TimeLimit::PerRound(time)
+    };
+
+    let game = match options.seed {
+        Some(seed) => Game::with_seed(config, seed),
+        None => Game::new(config),
+    };
+    let rounds = options.rounds.unwrap_or(3);
+    let mut session = TimedSession::new(game, rounds, Some(limit), SystemClock::start());
+    let console = Console::new();
+
+    println!(
+        "{}",
+        lang.text(Text::TimedIntro {
+            rounds,
+            seconds,
+            per_session: options.per_session,
+            min: config.min(),
+            max: config.max(),
+        })
+    );
+
+    let mut announced = 0;
+    while !session.is_finished() {
+        if announced != session.round() {
+            announced = session.round();
+            println!(
+                "{}",
+                lang.text(Text::TimedRound {
+                    round: announced,
+                    rounds,
+                })
+            );
+        }
+
+        let left = session.time_left().unwrap_or_default();
+        println!(
+            "{}",
+            lang.text(Text::TimedPrompt {
+                seconds_left: left.as_secs_f64().ceil() as u64,
+            })
+        );
+
+        let progress = match console.read_timeout(left) {
+            None => match session.expire() {
+                Some(result) => Progress::RoundOver(result),
+                None => continue,
             },
+            Some(Input::Line(line)) => match parse_guess(&line) {
+                Ok(guess) => session.guess(guess),
+                Err(e) => {
+                    println!("{}", lang.text(Text::Invalid(&e)));
+                    continue;
+                }
+            },
+            Some(Input::Eof | Input::Interrupt) => {
+                println!("{}", lang.text(Text::SessionStopped));
+                break;
+            }
         };
-        // guess = "Updated Value".to_string(); // because it is mutable
-        println!("You guessed: {}", guess);
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too Small!"),
-            Ordering::Greater => println!("Too Big!!"),
-            Ordering::Equal => {
-                println!("You Win!");
+
+        match progress {
+            Progress::Feedback(Outcome::TooSmall) => println!("{}", lang.text(Text::TooSmall)),
+            Progress::Feedback(Outcome::TooBig) => println!("{}", lang.text(Text::TooBig)),
+            Progress::Feedback(_) => {}
+            Progress::RoundOver(result) => {
+                record_round(
+                    options.name.as_deref(),
+                    &config,
+                    result.attempts,
+                    result.end == RoundEnd::Won,
+                    result.elapsed,
+                );
+                println!(
+                    "{}",
+                    lang.text(match result.end {
+                        RoundEnd::Won => Text::TimedWon {
+                            attempts: result.attempts,
+                            seconds: result.elapsed.as_secs_f64(),
+                            score: result.score,
+                        },
+                        RoundEnd::Lost => Text::Lost {
+                            secret: result.secret,
+                        },
+                        RoundEnd::TimedOut => Text::TimeUp {
+                            secret: result.secret,
+                        },
+                    })
+                )
+            }
+        }
+    }
+
+    println!();
+    print!("{}", ScoreTable(session.results()));
+}
+
+fn http(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let timeout = Duration::from_secs(options.ttl.unwrap_or(15 * 60));
+
+    let server = ApiServer::bind(("0.0.0.0", options.port.unwrap_or(8080)), timeout)
+        .unwrap_or_else(|e| fail(&format!("Could not start the HTTP API: {e}")));
+    let addr = server
+        .local_addr()
+        .unwrap_or_else(|e| fail(&format!("Could not start the HTTP API: {e}")));
+
+    println!(
+        "HTTP API listening on http://{addr}, idle rounds expire after {}s",
+        timeout.as_secs()
+    );
+    if let Err(e) = server.serve() {
+        fail(&format!("The HTTP API stopped: {e}"));
+    }
+}
+
+fn evil(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+
+    if options.compare {
+        return compare_against_evil(&options, config);
+    }
+
+    let lang = options.lang.unwrap_or_else(Lang::from_env);
+    let mut host = EvilHost::new(config);
+
+    println!("{}", lang.text(Text::EvilIntro));
+    println!(
+        "{}",
+        lang.text(Text::EvilRange {
+            min: config.min(),
+            max: config.max(),
+        })
+    );
+
+    let console = Console::new();
+    let started = Instant::now();
+
+    loop {
+        let remaining = host.remaining();
+        println!(
+            "{}",
+            lang.text(Text::EvilPrompt {
+                possible: remaining.len(),
+            })
+        );
+
+        let Some(guess) = read_valid(&console, |line| {
+            parse_guess(line).map_err(|e| lang.text(Text::Invalid(&e)))
+        }) else {
+            println!("{}", lang.text(Text::EvilBye));
+            return;
+        };
+
+        match host.check(guess) {
+            Outcome::Invalid => continue,
+            Outcome::TooSmall => println!("{}", lang.text(Text::TooSmall)),
+            Outcome::TooBig => println!("{}", lang.text(Text::TooBig)),
+            Outcome::Won { attempts } => {
+                println!("{}", lang.text(Text::Won { attempts }));
+                record_round(
+                    options.name.as_deref(),
+                    &config,
+                    attempts,
+                    true,
+                    started.elapsed(),
+                );
+                println!(
+                    "{}",
+                    lang.text(Text::EvilOptimal {
+                        attempts: strategy::optimal_worst_case(config.range_size()),
+                    })
+                );
                 break;
             }
+            Outcome::Lost { secret } => {
+                println!("{}", lang.text(Text::Lost { secret }));
+                let attempts = config.max_attempts().unwrap_or_default();
+                record_round(
+                    options.name.as_deref(),
+                    &config,
+                    attempts,
+                    false,
+                    started.elapsed(),
+                );
+                break;
+            }
+        }
+    }
+}
+
+fn compare_against_evil(options: &Options, config: Config) {
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let rounds = options.rounds.unwrap_or(100);
+    let optimal = strategy::optimal_worst_case(config.range_size());
+
+    println!(
+        "{rounds} rounds against the evil host, range {}..={} ({} numbers)",
+        config.min(),
+        config.max(),
+        config.range_size()
+    );
+    println!(
+        "{:<10} {:>6} {:>8} {:>6}",
+        "strategy", "worst", "mean", "lost"
+    );
+
+    for name in strategy::STRATEGY_NAMES {
+        let mut strategy = strategy::by_name(name, seed).expect("listed strategies exist");
+        let report = simulate_against(strategy.as_mut(), &mut EvilHost::new(config), rounds);
+
+        let worst = report
+            .worst()
+            .map_or_else(|| String::from("-"), |worst| worst.to_string());
+        let mean = report
+            .mean()
+            .map_or_else(|| String::from("-"), |mean| format!("{mean:.2}"));
+        println!("{name:<10} {worst:>6} {mean:>8} {:>6}", report.lost);
+    }
+
+    println!("No strategy can be sure to win in fewer than {optimal} attempts.");
+}
+
+fn liar_game(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    refuse_lang(&options);
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+    let lies = options.lies.unwrap_or(1);
+
+    if options.report {
+        println!(
+            "Questions needed for {} numbers when the host may lie",
+            config.range_size()
+        );
+        print!("{}", CostTable(&liar::lie_costs(&config, lies)));
+        return;
+    }
+
+    let mut game = match (options.evil, options.seed) {
+        (true, _) => LiarGame::adversary(config, lies),
+        (false, Some(seed)) => LiarGame::with_seed(config, lies, seed),
+        (false, None) => LiarGame::new(config, lies),
+    };
+    let mut solver = options.solve.then(|| liar::Solver::new(&config, lies));
+    let console = Console::new();
+
+    println!(
+        "Guess the number! It is between {} and {}, but I may lie up to {lies} {}.",
+        config.min(),
+        config.max(),
+        if lies == 1 { "time" } else { "times" }
+    );
+    println!(
+        "Ask with a number to hear whether mine is higher, answer with `= number` once you are sure."
+    );
+    println!(
+        "Even without lies you need {} questions, with them {}.",
+        liar::questions_needed(config.range_size(), 0),
+        liar::questions_needed(config.range_size(), lies)
+    );
+
+    let verdict = loop {
+        let next = match solver.as_ref() {
+            Some(solver) => {
+                let next = solver
+                    .next_move()
+                    .unwrap_or_else(|| fail("The solver found no number that fits the answers"));
+                match next {
+                    Move::Ask(threshold) => println!("Solver asks: higher than {threshold}?"),
+                    Move::Claim(number) => println!("Solver answers {number}"),
+                }
+                next
+            }
+            None => {
+                println!("Please input your question!");
+                let next = read_valid(&console, |line| {
+                    match line.trim().strip_prefix('=') {
+                        Some(claim) => parse_guess(claim).map(Move::Claim),
+                        None => parse_guess(line).map(Move::Ask),
+                    }
+                    .map_err(|e| format!("{e} Try again!"))
+                });
+                match next {
+                    Some(next) => next,
+                    None => {
+                        println!("Bye! The round ended without an answer.");
+                        return;
+                    }
+                }
+            }
+        };
+
+        match next {
+            Move::Ask(threshold) => {
+                let Some(reply) = game.ask(threshold) else {
+                    break None;
+                };
+                println!("{reply}!");
+                if let Some(solver) = solver.as_mut() {
+                    solver.feedback(threshold, reply);
+                }
+            }
+            Move::Claim(number) => break Some(game.claim(number)),
+        }
+    };
+
+    match verdict {
+        Some(Verdict::Right { questions }) => {
+            println!("You Win! It took you {questions} questions.")
+        }
+        Some(Verdict::Wrong { secret }) => println!("Wrong! The secret number was {secret}."),
+        None => {}
+    }
+
+    let told = game.lies().unwrap_or_default();
+    if told.is_empty() {
+        println!("I never lied.");
+    } else {
+        let told: Vec<String> = told.iter().map(usize::to_string).collect();
+        println!("I lied on question {}.", told.join(", "));
+    }
+}
+
+fn daily_challenge(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    let today = Date::today();
+    let puzzle = Puzzle::for_date(options.date.unwrap_or(today));
+    let config = puzzle.config;
+    let name = player_name(&options);
+    let lang = options.lang.unwrap_or_else(Lang::from_env);
+    let log = DailyLog::default_path().map(DailyLog::open);
+
+    println!(
+        "{}",
+        lang.text(Text::DailyIntro {
+            date: puzzle.date,
+            min: config.min(),
+            max: config.max(),
+        })
+    );
+
+    // Only today's puzzle counts, and only the first go at it
+    let mut scored = puzzle.date == today;
+    if !scored {
+        println!("{}", lang.text(Text::DailyNotToday));
+    } else if let Some(log) = &log {
+        match log.result(today, &name) {
+            Ok(Some(result)) => {
+                println!(
+                    "{}",
+                    lang.text(Text::DailyAlreadyPlayed {
+                        name: &name,
+                        result,
+                    })
+                );
+                scored = false;
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Could not read {}: {e}", log.path().display()),
+        }
+    }
+
+    let mut game = puzzle.game();
+    let console = Console::new();
+    let started = Instant::now();
+    let mut claimed = false;
+
+    let result = loop {
+        println!(
+            "{}",
+            lang.text(Text::Prompt {
+                attempts_left: game.attempts_left(),
+            })
+        );
+
+        let Some(guess) = read_valid(&console, |line| {
+            parse_guess(line).map_err(|e| lang.text(Text::Invalid(&e)))
+        }) else {
+            // Before the day is claimed the secret would let the next try score a perfect round
+            if claimed || !scored {
+                let secret = puzzle.secret;
+                println!("{}", lang.text(Text::DailyGivingUp { secret }));
+            } else if game.attempts() == 0 {
+                println!("{}", lang.text(Text::DailyStoppedEarly));
+            } else {
+                // Without a log the day was never claimed, and the next try would still score
+                println!("{}", lang.text(Text::DailyStoppedHidden));
+            }
+            break DailyResult::Lost;
+        };
+
+        if scored && !claimed {
+            if let Some(log) = &log {
+                match log.claim(today, &name) {
+                    Ok(true) => claimed = true,
+                    Ok(false) => {
+                        println!("{}", lang.text(Text::DailyStartedElsewhere));
+                        scored = false;
+                    }
+                    Err(e) => eprintln!("Could not write {}: {e}", log.path().display()),
+                }
+            }
+        }
+
+        match game.check(guess) {
+            Outcome::Invalid => continue,
+            Outcome::TooSmall => println!("{}", lang.text(Text::TooSmall)),
+            Outcome::TooBig => println!("{}", lang.text(Text::TooBig)),
+            Outcome::Won { attempts } => {
+                println!("{}", lang.text(Text::Won { attempts }));
+                break DailyResult::Won { attempts };
+            }
+            Outcome::Lost { secret } => {
+                println!("{}", lang.text(Text::Lost { secret }));
+                break DailyResult::Lost;
+            }
         }
+    };
+
+    if claimed {
+        if let Some(log) = &log {
+            if let Err(e) = log.finish(today, &name, result) {
+                eprintln!("Could not write {}: {e}", log.path().display());
+            }
+        }
+    }
+    // Replays would let a player who knows the answer pad their history, as on the leaderboard
+    if scored && game.is_over() {
+        let won = matches!(result, DailyResult::Won { .. });
+        record_round(
+            Some(&name),
+            &config,
+            game.attempts(),
+            won,
+            started.elapsed(),
+        );
     }
 
-    // let x = 5;
-    // let y = 8;
-    // println!("x is {x} and y + 2 = {}", y + 2);
+    if game.attempts() > 0 {
+        println!();
+        println!("{}", daily::share_text(puzzle.date, &game));
+    }
+}
+
+fn tournament(args: &[String]) {
+    let options = parse_options(args).unwrap_or_else(|message| fail(&message));
+    let config = build_config(&options).unwrap_or_else(|message| fail(&message));
+
+    let names: Vec<String> = options
+        .players
+        .as_deref()
+        .unwrap_or_else(|| fail("Name the players with --players ann,bob"))
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .collect();
+    let lang = options.lang.unwrap_or_else(Lang::from_env);
+    let mode = if options.shared {
+        Mode::Shared
+    } else {
+        Mode::Separate
+    };
+    let mut tournament = Tournament::new(
+        names.clone(),
+        config,
+        mode,
+        options.rounds.unwrap_or(3),
+        options.seed,
+    )
+    .unwrap_or_else(|e| fail(&e.to_string()));
+    let config = *tournament.config();
+
+    println!(
+        "{}",
+        lang.text(Text::TournamentIntro {
+            rounds: tournament.rounds(),
+            players: &names,
+            shared: mode == Mode::Shared,
+        })
+    );
+    println!(
+        "{}",
+        lang.text(Text::TournamentRules {
+            min: config.min(),
+            max: config.max(),
+            attempts: config.max_attempts().unwrap_or_default(),
+        })
+    );
+
+    let console = Console::new();
+    let mut round = 0;
+    let mut round_started = Instant::now();
+
+    while let Some(turn) = tournament.turn() {
+        if turn.round != round {
+            round = turn.round;
+            round_started = Instant::now();
+            println!();
+            println!("{}", lang.text(Text::TournamentRound { round }));
+        }
+        let player = turn.player.to_string();
+        println!(
+            "{}",
+            lang.text(Text::PlayerPrompt {
+                player: &player,
+                attempts_left: turn.attempts_left,
+            })
+        );
+
+        let Input::Line(line) = console.read() else {
+            println!("{}", lang.text(Text::TournamentStopped));
+            break;
+        };
+
+        let summary = if line.trim().eq_ignore_ascii_case("forfeit") {
+            println!("{}", lang.text(Text::Forfeits { player: &player }));
+            tournament.forfeit()
+        } else {
+            let guess = match parse_guess(&line) {
+                Ok(guess) => guess,
+                Err(e) => {
+                    println!("{}", lang.text(Text::Invalid(&e)));
+                    continue;
+                }
+            };
+
+            let (outcome, summary) = tournament.guess(guess);
+            match outcome {
+                Outcome::Invalid => {}
+                Outcome::TooSmall => println!("{}", lang.text(Text::TooSmall)),
+                Outcome::TooBig => println!("{}", lang.text(Text::TooBig)),
+                Outcome::Won { attempts } => println!(
+                    "{}",
+                    lang.text(Text::PlayerFound {
+                        player: &player,
+                        attempts,
+                    })
+                ),
+                // The others may still be hunting the same number, it stays hidden
+                Outcome::Lost { .. } => {
+                    println!("{}", lang.text(Text::PlayerOut { player: &player }))
+                }
+            }
+            summary
+        };
+
+        if let Some(summary) = summary {
+            let round = summary.round;
+            println!("{}", lang.text(Text::RoundOver { round }));
+            for result in &summary.players {
+                let won = matches!(result.status, Status::Found { .. });
+                let elapsed = round_started.elapsed();
+                record_round(Some(&result.name), &config, result.attempts, won, elapsed);
+                println!(
+                    "{}",
+                    lang.text(Text::PlayerResult {
+                        player: &result.name,
+                        status: result.status,
+                        secret: result.secret,
+                    })
+                );
+            }
+            println!("{}", lang.text(Text::RoundWinners(&summary.winners)));
+            if !tournament.is_finished() {
+                println!();
+                print!("{}", StandingsTable(&tournament.standings()));
+            }
+        }
+    }
+
+    if tournament.is_finished() {
+        println!();
+        println!("{}", lang.text(Text::FinalStandings));
+        print!("{}", StandingsTable(&tournament.standings()));
+    }
+}
+
+fn verify(args: &[String]) {
+    let [commitment, secret, salt] = args else {
+        fail("Usage: guessing_game verify <commitment> <secret> <salt>");
+    };
+    let secret: i128 = parse_value("secret", secret).unwrap_or_else(|message| fail(&message));
+
+    if fair::verify(commitment, secret, salt) {
+        println!("The commitment matches, the secret was {secret} all along.");
+    } else {
+        println!("The commitment does NOT match this secret and salt!");
+        process::exit(1);
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .map(String::as_str)
+                .ok_or(format!("{arg} needs a value"))
+        };
+
+        match arg.as_str() {
+            "--seed" => options.seed = Some(parse_value(arg, value()?)?),
+            "--difficulty" => {
+                options.difficulty = Some(value()?.parse().map_err(|e| format!("{e}"))?)
+            }
+            "--min" => options.min = Some(parse_value(arg, value()?)?),
+            "--max" => options.max = Some(parse_value(arg, value()?)?),
+            "--max-attempts" => options.max_attempts = Some(parse_value(arg, value()?)?),
+            "--name" => options.name = Some(value()?.to_string()),
+            "--top" => options.top = Some(parse_value(arg, value()?)?),
+            "--strategy" => options.strategy = Some(value()?.to_string()),
+            "--rounds" => options.rounds = Some(parse_value(arg, value()?)?),
+            "--port" => options.port = Some(parse_value(arg, value()?)?),
+            "--addr" => options.addr = Some(value()?.to_string()),
+            "--length" => options.length = Some(parse_value(arg, value()?)?),
+            "--repeats" => options.repeats = true,
+            "--solve" => options.solve = true,
+            "--tui" => options.tui = true,
+            "--protocol" => options.protocol = Some(value()?.to_string()),
+            "--resume" => options.resume = true,
+            "--time" => options.time = Some(parse_value(arg, value()?)?),
+            "--per-session" => options.per_session = true,
+            "--ttl" => options.ttl = Some(parse_value(arg, value()?)?),
+            "--compare" => options.compare = true,
+            "--lies" => options.lies = Some(parse_value(arg, value()?)?),
+            "--evil" => options.evil = true,
+            "--report" => options.report = true,
+            "--date" => options.date = Some(value()?.parse()?),
+            "--players" => options.players = Some(value()?.to_string()),
+            "--shared" => options.shared = true,
+            "--coach" => options.coach = true,
+            "--lang" => options.lang = Some(value()?.parse()?),
+            other => return Err(format!("Unknown argument `{other}`")),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Modes without a message catalog would silently ignore `--lang`.
+fn refuse_lang(options: &Options) {
+    if options.lang.is_some() {
+        fail("--lang only works for play, timed, evil, daily, tournament and join, this mode is in English");
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} got an invalid value `{value}`"))
+}
+
+fn build_config(options: &Options) -> Result<Config, String> {
+    Config::resolve(
+        options.difficulty,
+        options.min,
+        options.max,
+        options.max_attempts,
+    )
+    .map_err(|e| e.to_string())
 }
@@ -0,0 +1,755 @@
+/*
+ * LAN multiplayer, several players race to find the same secret.
+ *
+ * Line based protocol, one message per line:
+ *   client -> server   NAME <name>
+ *                      GUESS <number>
+ *   server -> client   WELCOME <min> <max> <max attempts or ->
+ *                      JOINED <name>
+ *                      GUESSED <too_small|too_big|correct> <guess> <name>
+ *                      OUT <name>                      (that player ran out of attempts)
+ *                      LEFT <name>                     (that player disconnected)
+ *                      WINNER <attempts> <secret> <name>
+ *                      OVER <secret>                   (everybody ran out of attempts)
+ *                      ERROR <message>
+ * Names go last so they may contain spaces. Everything but WELCOME and ERROR is broadcast to all players.
+ * Lines longer than `MAX_LINE` bytes end the connection.
+ */
+
+use crate::{
+    config::Config,
+    game::{Game, Outcome},
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// Longest line either side reads, the same cap as the HTTP server.
+const MAX_LINE: usize = 8 * 1024;
+
+/// Something that happened in the round, as seen by the server or a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Welcome {
+        min: i128,
+        max: i128,
+        max_attempts: Option<u32>,
+    },
+    Joined {
+        name: String,
+    },
+    Guessed {
+        name: String,
+        guess: i128,
+        /// `guess.cmp(&secret)`
+        ordering: Ordering,
+    },
+    Out {
+        name: String,
+    },
+    Left {
+        name: String,
+    },
+    Winner {
+        name: String,
+        attempts: u32,
+        secret: i128,
+    },
+    Over {
+        secret: i128,
+    },
+    Error {
+        message: String,
+    },
+}
+
+impl Event {
+    pub fn to_line(&self) -> String {
+        match self {
+            Event::Welcome {
+                min,
+                max,
+                max_attempts,
+            } => match max_attempts {
+                Some(limit) => format!("WELCOME {min} {max} {limit}"),
+                None => format!("WELCOME {min} {max} -"),
+            },
+            Event::Joined { name } => format!("JOINED {name}"),
+            Event::Guessed {
+                name,
+                guess,
+                ordering,
+            } => format!("GUESSED {} {guess} {name}", ordering_word(*ordering)),
+            Event::Out { name } => format!("OUT {name}"),
+            Event::Left { name } => format!("LEFT {name}"),
+            Event::Winner {
+                name,
+                attempts,
+                secret,
+            } => format!("WINNER {attempts} {secret} {name}"),
+            Event::Over { secret } => format!("OVER {secret}"),
+            Event::Error { message } => format!("ERROR {message}"),
+        }
+    }
+
+    pub fn parse(line: &str) -> Option<Self> {
+        let (command, rest) = line.trim_end().split_once(' ')?;
+
+        let event = match command {
+            "WELCOME" => {
+                let mut fields = rest.split(' ');
+                let min = fields.next()?.parse().ok()?;
+                let max = fields.next()?.parse().ok()?;
+                let max_attempts = match fields.next()? {
+                    "-" => None,
+                    limit => Some(limit.parse().ok()?),
+                };
+                Event::Welcome {
+                    min,
+                    max,
+                    max_attempts,
+                }
+            }
+            "JOINED" => Event::Joined {
+                name: rest.to_string(),
+            },
+            "GUESSED" => {
+                let mut fields = rest.splitn(3, ' ');
+                let ordering = parse_ordering_word(fields.next()?)?;
+                let guess = fields.next()?.parse().ok()?;
+                Event::Guessed {
+                    name: fields.next()?.to_string(),
+                    guess,
+                    ordering,
+                }
+            }
+            "OUT" => Event::Out {
+                name: rest.to_string(),
+            },
+            "LEFT" => Event::Left {
+                name: rest.to_string(),
+            },
+            "WINNER" => {
+                let mut fields = rest.splitn(3, ' ');
+                let attempts = fields.next()?.parse().ok()?;
+                let secret = fields.next()?.parse().ok()?;
+                Event::Winner {
+                    name: fields.next()?.to_string(),
+                    attempts,
+                    secret,
+                }
+            }
+            "OVER" => Event::Over {
+                secret: rest.parse().ok()?,
+            },
+            "ERROR" => Event::Error {
+                message: rest.to_string(),
+            },
+            _ => return None,
+        };
+
+        Some(event)
+    }
+}
+
+fn ordering_word(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "too_small",
+        Ordering::Greater => "too_big",
+        Ordering::Equal => "correct",
+    }
+}
+
+fn parse_ordering_word(word: &str) -> Option<Ordering> {
+    match word {
+        "too_small" => Some(Ordering::Less),
+        "too_big" => Some(Ordering::Greater),
+        "correct" => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+/// How a served round ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundSummary {
+    pub secret: i128,
+    /// Name and attempts of the winner, `None` if every player ran out of attempts.
+    pub winner: Option<(String, u32)>,
+}
+
+enum Message {
+    Connected(usize, TcpStream),
+    Line(usize, String),
+    Gone(usize),
+}
+
+struct Player {
+    stream: TcpStream,
+    name: Option<String>,
+    game: Option<Game>,
+}
+
+/// Hosts a single round, every player gets their own attempt counter for the shared secret.
+pub struct Server {
+    listener: TcpListener,
+    config: Config,
+    secret: i128,
+}
+
+impl Server {
+    pub fn bind(addr: impl ToSocketAddrs, config: Config, seed: Option<u64>) -> io::Result<Self> {
+        let mut rng = match seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_entropy(),
+        };
+
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            secret: rng.gen_range(config.min()..=config.max()),
+            config,
+        })
+    }
+
+    /// Useful after binding to port 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Runs the round until somebody wins or every player is out of attempts.
+    /// `on_event` sees every broadcast, the `serve` command uses it to print the round.
+    pub fn run(self, mut on_event: impl FnMut(&Event)) -> io::Result<RoundSummary> {
+        let (sender, receiver) = mpsc::channel();
+        let local_addr = self.listener.local_addr()?;
+        let listener = self.listener.try_clone()?;
+        thread::spawn(move || accept_loop(listener, sender));
+
+        let mut players: BTreeMap<usize, Player> = BTreeMap::new();
+
+        loop {
+            let message = match receiver.recv_timeout(Duration::from_millis(50)) {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(io::Error::other("the listener stopped"))
+                }
+            };
+
+            let events = match message {
+                Message::Connected(id, stream) => {
+                    let mut player = Player {
+                        stream,
+                        name: None,
+                        game: None,
+                    };
+                    send(
+                        &mut player.stream,
+                        &Event::Welcome {
+                            min: self.config.min(),
+                            max: self.config.max(),
+                            max_attempts: self.config.max_attempts(),
+                        },
+                    );
+                    players.insert(id, player);
+                    continue;
+                }
+                Message::Gone(id) => {
+                    // Somebody who never sent NAME leaves without anybody noticing
+                    let Some(Player {
+                        name: Some(name), ..
+                    }) = players.remove(&id)
+                    else {
+                        continue;
+                    };
+
+                    let mut events = vec![Event::Left { name }];
+                    if everybody_out(&players) {
+                        events.push(Event::Over {
+                            secret: self.secret,
+                        });
+                    }
+                    events
+                }
+                Message::Line(id, line) => match self.handle_line(&mut players, id, &line) {
+                    Ok(events) => events,
+                    Err(message) => {
+                        if let Some(player) = players.get_mut(&id) {
+                            send(&mut player.stream, &Event::Error { message });
+                        }
+                        continue;
+                    }
+                },
+            };
+
+            for event in &events {
+                on_event(event);
+                for player in players.values_mut().filter(|p| p.name.is_some()) {
+                    send(&mut player.stream, event);
+                }
+            }
+
+            let summary = match events.last() {
+                Some(Event::Winner { name, attempts, .. }) => Some(RoundSummary {
+                    secret: self.secret,
+                    winner: Some((name.clone(), *attempts)),
+                }),
+                Some(Event::Over { .. }) => Some(RoundSummary {
+                    secret: self.secret,
+                    winner: None,
+                }),
+                _ => None,
+            };
+
+            if let Some(summary) = summary {
+                // Closing the sockets lets every client see the end of the stream
+                for player in players.values() {
+                    let _ = player.stream.shutdown(Shutdown::Both);
+                }
+                wake_accept_loop(local_addr, receiver);
+                return Ok(summary);
+            }
+        }
+    }
+
+    fn handle_line(
+        &self,
+        players: &mut BTreeMap<usize, Player>,
+        id: usize,
+        line: &str,
+    ) -> Result<Vec<Event>, String> {
+        let (command, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+
+        match command {
+            "NAME" => {
+                let name = rest.trim();
+                if name.is_empty() {
+                    return Err(String::from("NAME needs a name"));
+                }
+                if players.values().any(|p| p.name.as_deref() == Some(name)) {
+                    return Err(format!("the name {name} is already taken"));
+                }
+
+                let player = players.get_mut(&id).ok_or("unknown player")?;
+                if player.name.is_some() {
+                    return Err(String::from("you already have a name"));
+                }
+                player.name = Some(name.to_string());
+                player.game = Some(Game::with_secret(self.config, self.secret));
+
+                Ok(vec![Event::Joined {
+                    name: name.to_string(),
+                }])
+            }
+            "GUESS" => {
+                let player = players.get_mut(&id).ok_or("unknown player")?;
+                let (Some(name), Some(game)) = (player.name.clone(), player.game.as_mut()) else {
+                    return Err(String::from("send NAME before guessing"));
+                };
+                let guess: i128 = rest
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("`{}` is not a number", rest.trim()))?;
+
+                let outcome = game.check(guess);
+                let mut events = vec![Event::Guessed {
+                    name: name.clone(),
+                    guess,
+                    ordering: game.compare(guess),
+                }];
+
+                match outcome {
+                    Outcome::Invalid => return Err(String::from("you are out of attempts")),
+                    Outcome::Won { attempts } => events.push(Event::Winner {
+                        name,
+                        attempts,
+                        secret: self.secret,
+                    }),
+                    Outcome::Lost { .. } => {
+                        events.push(Event::Out { name });
+                        if everybody_out(players) {
+                            events.push(Event::Over {
+                                secret: self.secret,
+                            });
+                        }
+                    }
+                    Outcome::TooSmall | Outcome::TooBig => {}
+                }
+
+                Ok(events)
+            }
+            _ => Err(format!("unknown command `{command}`")),
+        }
+    }
+}
+
+/// Whether the round is lost for everybody, there must be somebody left who played.
+fn everybody_out(players: &BTreeMap<usize, Player>) -> bool {
+    let mut games = players.values().filter_map(|p| p.game.as_ref()).peekable();
+    games.peek().is_some() && games.all(Game::is_over)
+}
+
+fn send(stream: &mut TcpStream, event: &Event) {
+    // A player that hung up is cleaned up by its reader thread, nothing to do here
+    let _ = writeln!(stream, "{}", event.to_line());
+}
+
+/// The accept thread is blocked in `incoming()`, one last connection lets it notice the round is over
+/// and drop its handle on the port.
+fn wake_accept_loop(mut addr: SocketAddr, receiver: mpsc::Receiver<Message>) {
+    drop(receiver);
+    if addr.ip().is_unspecified() {
+        addr.set_ip(match addr {
+            SocketAddr::V4(_) => Ipv4Addr::LOCALHOST.into(),
+            SocketAddr::V6(_) => Ipv6Addr::LOCALHOST.into(),
+        });
+    }
+    let _ = TcpStream::connect(addr);
+}
+
+fn accept_loop(listener: TcpListener, sender: mpsc::Sender<Message>) {
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Ok(reader) = stream.try_clone() else {
+            continue;
+        };
+        if sender.send(Message::Connected(id, stream)).is_err() {
+            // The round is over
+            return;
+        }
+
+        let sender = sender.clone();
+        thread::spawn(move || {
+            let mut reader = BufReader::new(reader);
+            while let Ok(Some(line)) = read_line(&mut reader) {
+                if sender.send(Message::Line(id, line)).is_err() {
+                    return;
+                }
+            }
+            let _ = sender.send(Message::Gone(id));
+        });
+    }
+}
+
+/// One line without its line break, `None` at the end of the input. Lines of `MAX_LINE`
+/// bytes or more are an error, the rest of them is never read.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if reader.by_ref().take(MAX_LINE as u64).read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+
+    match line.strip_suffix('\n') {
+        Some(line) => Ok(Some(line.strip_suffix('\r').unwrap_or(line).to_string())),
+        None if line.len() == MAX_LINE => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("a line is longer than {MAX_LINE} bytes"),
+        )),
+        // The last line of the input
+        None => Ok(Some(line)),
+    }
+}
+
+/// A player connected to a `Server`.
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    pub fn connect(addr: impl ToSocketAddrs, name: &str) -> io::Result<Self> {
+        let writer = TcpStream::connect(addr)?;
+        let mut client = Self {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+        };
+
+        writeln!(client.writer, "NAME {name}")?;
+        Ok(client)
+    }
+
+    pub fn guess(&mut self, guess: i128) -> io::Result<()> {
+        writeln!(self.writer, "GUESS {guess}")
+    }
+
+    /// A second handle for sending guesses, so one thread can read events while another types.
+    pub fn guesser(&self) -> io::Result<Guesser> {
+        Ok(Guesser {
+            writer: self.writer.try_clone()?,
+        })
+    }
+
+    /// Blocks for the next message, `None` once the server closed the round.
+    pub fn next_event(&mut self) -> io::Result<Option<Event>> {
+        loop {
+            let Some(line) = read_line(&mut self.reader)? else {
+                return Ok(None);
+            };
+            // Unknown lines are skipped so older clients keep working with newer servers
+            if let Some(event) = Event::parse(&line) {
+                return Ok(Some(event));
+            }
+        }
+    }
+}
+
+pub struct Guesser {
+    writer: TcpStream,
+}
+
+impl Guesser {
+    pub fn guess(&mut self, guess: i128) -> io::Result<()> {
+        writeln!(self.writer, "GUESS {guess}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serve(config: Config) -> (SocketAddr, mpsc::Receiver<RoundSummary>) {
+        let server = Server::bind("127.0.0.1:0", config, Some(7)).unwrap();
+        let addr = server.local_addr().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || sender.send(server.run(|_| {}).unwrap()));
+        (addr, receiver)
+    }
+
+    fn expect(client: &mut Client, event: Event) {
+        assert_eq!(client.next_event().unwrap(), Some(event));
+    }
+
+    /// Connects `name` and waits until everybody already in the round saw it join.
+    fn join(addr: SocketAddr, config: Config, name: &str, others: &mut [&mut Client]) -> Client {
+        let mut client = Client::connect(addr, name).unwrap();
+        expect(
+            &mut client,
+            Event::Welcome {
+                min: config.min(),
+                max: config.max(),
+                max_attempts: config.max_attempts(),
+            },
+        );
+        let joined = Event::Joined {
+            name: name.to_string(),
+        };
+        expect(&mut client, joined.clone());
+        for other in others {
+            expect(other, joined.clone());
+        }
+        client
+    }
+
+    fn summary(receiver: &mpsc::Receiver<RoundSummary>) -> RoundSummary {
+        receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("the round should be over")
+    }
+
+    fn guessed(name: &str, guess: i128, ordering: Ordering) -> Event {
+        Event::Guessed {
+            name: name.to_string(),
+            guess,
+            ordering,
+        }
+    }
+
+    #[test]
+    fn guesses_are_broadcast_and_the_first_to_hit_wins() {
+        let config = Config::custom(1, 100).unwrap();
+        let (addr, receiver) = serve(config);
+        let mut ann = join(addr, config, "ann", &mut []);
+        let mut bob = join(addr, config, "bob", &mut [&mut ann]);
+
+        bob.guess(101).unwrap();
+        for client in [&mut ann, &mut bob] {
+            expect(client, guessed("bob", 101, Ordering::Greater));
+        }
+
+        // Ann searches with what the broadcasts tell her, bob watches
+        let (mut low, mut high) = (config.min(), config.max());
+        let winner = loop {
+            let guess = low + (high - low) / 2;
+            ann.guess(guess).unwrap();
+            let event = ann.next_event().unwrap().unwrap();
+            assert_eq!(bob.next_event().unwrap().as_ref(), Some(&event));
+            match event {
+                Event::Guessed {
+                    ordering: Ordering::Less,
+                    ..
+                } => low = guess + 1,
+                Event::Guessed {
+                    ordering: Ordering::Greater,
+                    ..
+                } => high = guess - 1,
+                Event::Guessed {
+                    ordering: Ordering::Equal,
+                    ..
+                } => break ann.next_event().unwrap().unwrap(),
+                event => panic!("unexpected {event:?}"),
+            }
+        };
+
+        let summary = summary(&receiver);
+        let Event::Winner { name, attempts, .. } = &winner else {
+            panic!("expected a winner, got {winner:?}");
+        };
+        assert_eq!(name, "ann");
+        assert_eq!(summary.winner, Some((name.clone(), *attempts)));
+        assert_eq!(
+            winner,
+            Event::Winner {
+                name: String::from("ann"),
+                attempts: *attempts,
+                secret: summary.secret,
+            }
+        );
+        expect(&mut bob, winner);
+        assert_eq!(ann.next_event().unwrap(), None);
+        assert_eq!(bob.next_event().unwrap(), None);
+    }
+
+    #[test]
+    fn the_round_is_over_when_everybody_is_out() {
+        let config = Config::custom(1, 100)
+            .unwrap()
+            .with_max_attempts(Some(1))
+            .unwrap();
+        let (addr, receiver) = serve(config);
+        let mut ann = join(addr, config, "ann", &mut []);
+        let mut bob = join(addr, config, "bob", &mut [&mut ann]);
+
+        ann.guess(0).unwrap();
+        bob.guess(101).unwrap();
+        // Ann's line may reach the server first or second, each player sees the same order
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            let event = ann.next_event().unwrap().unwrap();
+            expect(&mut bob, event.clone());
+            seen.push(event);
+        }
+        for (name, guess, ordering) in [("ann", 0, Ordering::Less), ("bob", 101, Ordering::Greater)]
+        {
+            let at = seen
+                .iter()
+                .position(|event| *event == guessed(name, guess, ordering))
+                .expect("every guess is broadcast");
+            assert_eq!(
+                seen[at + 1],
+                Event::Out {
+                    name: name.to_string()
+                }
+            );
+        }
+
+        let summary = summary(&receiver);
+        assert_eq!(summary.winner, None);
+        for client in [&mut ann, &mut bob] {
+            expect(
+                client,
+                Event::Over {
+                    secret: summary.secret,
+                },
+            );
+            assert_eq!(client.next_event().unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn events_survive_the_wire() {
+        for event in [
+            Event::Welcome {
+                min: -5,
+                max: 5,
+                max_attempts: None,
+            },
+            Event::Joined {
+                name: String::from("ann lee"),
+            },
+            guessed("bob", -3, Ordering::Less),
+            Event::Winner {
+                name: String::from("ann lee"),
+                attempts: 4,
+                secret: 17,
+            },
+            Event::Left {
+                name: String::from("bob"),
+            },
+            Event::Over { secret: 9 },
+        ] {
+            assert_eq!(Event::parse(&event.to_line()), Some(event));
+        }
+    }
+
+    #[test]
+    fn leaving_ends_the_round_when_the_rest_are_out() {
+        let config = Config::custom(1, 100)
+            .unwrap()
+            .with_max_attempts(Some(1))
+            .unwrap();
+        let (addr, receiver) = serve(config);
+        let mut ann = join(addr, config, "ann", &mut []);
+        let bob = join(addr, config, "bob", &mut [&mut ann]);
+
+        ann.guess(0).unwrap();
+        expect(&mut ann, guessed("ann", 0, Ordering::Less));
+        expect(
+            &mut ann,
+            Event::Out {
+                name: String::from("ann"),
+            },
+        );
+
+        drop(bob);
+        expect(
+            &mut ann,
+            Event::Left {
+                name: String::from("bob"),
+            },
+        );
+        let summary = summary(&receiver);
+        expect(
+            &mut ann,
+            Event::Over {
+                secret: summary.secret,
+            },
+        );
+        assert_eq!(ann.next_event().unwrap(), None);
+        assert_eq!(summary.winner, None);
+    }
+
+    #[test]
+    fn a_line_past_the_cap_drops_the_player() {
+        let config = Config::custom(1, 100).unwrap();
+        let (addr, _receiver) = serve(config);
+        let mut ann = join(addr, config, "ann", &mut []);
+        let mut bob = join(addr, config, "bob", &mut [&mut ann]);
+
+        writeln!(bob.writer, "GUESS {}", "1".repeat(MAX_LINE)).unwrap();
+        expect(
+            &mut ann,
+            Event::Left {
+                name: String::from("bob"),
+            },
+        );
+
+        let long = "a".repeat(MAX_LINE);
+        let mut reader = BufReader::new(long.as_bytes());
+        assert_eq!(
+            read_line(&mut reader).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        let mut reader = BufReader::new("GUESS 1\r\nGUESS 2".as_bytes());
+        assert_eq!(read_line(&mut reader).unwrap().as_deref(), Some("GUESS 1"));
+        assert_eq!(read_line(&mut reader).unwrap().as_deref(), Some("GUESS 2"));
+        assert_eq!(read_line(&mut reader).unwrap(), None);
+    }
+}
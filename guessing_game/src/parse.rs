@@ -0,0 +1,604 @@
+/*
+ * Guess parser, so players can type more than plain decimal:
+ *   42, -7, 1_000, 0x2A, 0b101010, 0o52
+ *   40+2, (50 - 8) * 1, 84 / 2, forty plus two, 6 times 7
+ *   half of 84, double 21, twice 21, minus 3, negative 3
+ *   forty two, forty-two, one hundred and five, two thousand
+ * Errors name the exact token that was not understood and where it is.
+ */
+
+use crate::i18n::{Lang, Text};
+use std::fmt;
+
+/// How deep brackets and prefixes may nest, the parser recurses once per level.
+const MAX_DEPTH: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    /// Something that is not a number, an operator or a known word.
+    UnknownToken {
+        token: String,
+        column: usize,
+    },
+    /// A known token in a place where it makes no sense, like `3 +` or `* 4`.
+    Unexpected {
+        token: String,
+        column: usize,
+    },
+    UnexpectedEnd,
+    DivisionByZero {
+        column: usize,
+    },
+    /// `half of 5` or `7 / 2`, guesses are whole numbers.
+    NotWhole {
+        column: usize,
+    },
+    Overflow,
+    /// Brackets or prefixes like `minus` nested deeper than `MAX_DEPTH`.
+    TooDeep {
+        column: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    /// The English text from the message catalog, players see it in their own language.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&Lang::English.text(Text::ParseError(self)))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Number(i128),
+    Op(Op),
+    Open,
+    Close,
+    Word(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    kind: Kind,
+    text: String,
+    /// 1-based, counted in characters
+    column: usize,
+}
+
+/// Parses one guess, see the top of this file for what is understood.
+pub fn parse_guess(input: &str) -> Result<i128, ParseError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut parser = Parser {
+        tokens,
+        next: 0,
+        depth: 0,
+    };
+    let value = parser.expression()?;
+
+    match parser.peek() {
+        None => Ok(value),
+        Some(token) => Err(parser.unexpected(token.clone())),
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        let kind = if c.is_whitespace() || c == ',' {
+            i += 1;
+            continue;
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            match parse_literal(&text) {
+                Some(number) => Kind::Number(number),
+                None if text.chars().all(|c| c.is_ascii_digit() || c == '_') => {
+                    return Err(ParseError::Overflow)
+                }
+                None => {
+                    return Err(ParseError::UnknownToken {
+                        token: text,
+                        column: start + 1,
+                    })
+                }
+            }
+        } else if c.is_alphabetic() {
+            // Hyphens between letters belong to the word, as in forty-two
+            while i < chars.len()
+                && (chars[i].is_alphabetic()
+                    || (chars[i] == '-' && chars.get(i + 1).is_some_and(|c| c.is_alphabetic())))
+            {
+                i += 1;
+            }
+            Kind::Word(chars[start..i].iter().collect::<String>().to_lowercase())
+        } else {
+            i += 1;
+            match c {
+                '+' => Kind::Op(Op::Add),
+                '-' | '−' => Kind::Op(Op::Sub),
+                '*' | '×' => Kind::Op(Op::Mul),
+                '/' | '÷' => Kind::Op(Op::Div),
+                '(' => Kind::Open,
+                ')' => Kind::Close,
+                _ => {
+                    return Err(ParseError::UnknownToken {
+                        token: c.to_string(),
+                        column: start + 1,
+                    })
+                }
+            }
+        };
+
+        tokens.push(Token {
+            kind,
+            text: chars[start..i].iter().collect(),
+            column: start + 1,
+        });
+    }
+
+    Ok(tokens)
+}
+
+/// Plain decimal or a 0x / 0b / 0o literal, underscores are allowed as separators.
+fn parse_literal(text: &str) -> Option<i128> {
+    let digits = text.replace('_', "");
+    let lower = digits.to_ascii_lowercase();
+
+    let (radix, body) = if let Some(body) = lower.strip_prefix("0x") {
+        (16, body)
+    } else if let Some(body) = lower.strip_prefix("0b") {
+        (2, body)
+    } else if let Some(body) = lower.strip_prefix("0o") {
+        (8, body)
+    } else {
+        (10, lower.as_str())
+    };
+
+    if body.is_empty() {
+        return None;
+    }
+    i128::from_str_radix(body, radix).ok()
+}
+
+fn small_number(word: &str) -> Option<i128> {
+    let value = match word {
+        "zero" => 0,
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        "eleven" => 11,
+        "twelve" => 12,
+        "thirteen" => 13,
+        "fourteen" => 14,
+        "fifteen" => 15,
+        "sixteen" => 16,
+        "seventeen" => 17,
+        "eighteen" => 18,
+        "nineteen" => 19,
+        "twenty" => 20,
+        "thirty" => 30,
+        "forty" => 40,
+        "fifty" => 50,
+        "sixty" => 60,
+        "seventy" => 70,
+        "eighty" => 80,
+        "ninety" => 90,
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn scale(word: &str) -> Option<i128> {
+    match word {
+        "hundred" => Some(100),
+        "thousand" => Some(1_000),
+        "million" => Some(1_000_000),
+        "billion" => Some(1_000_000_000),
+        "trillion" => Some(1_000_000_000_000),
+        _ => None,
+    }
+}
+
+fn is_number_word(word: &str) -> bool {
+    word.split('-')
+        .all(|part| small_number(part).is_some() || scale(part).is_some())
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+    /// How many `unary` calls are open, every bracket and prefix goes through there.
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn peek_word(&self) -> Option<&str> {
+        match self.peek() {
+            Some(Token {
+                kind: Kind::Word(word),
+                ..
+            }) => Some(word),
+            _ => None,
+        }
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    fn unexpected(&self, token: Token) -> ParseError {
+        match token.kind {
+            Kind::Word(ref word) if !is_known_word(word) => ParseError::UnknownToken {
+                token: token.text,
+                column: token.column,
+            },
+            _ => ParseError::Unexpected {
+                token: token.text,
+                column: token.column,
+            },
+        }
+    }
+
+    /// `+`, `-`, plus, minus
+    fn additive_op(&mut self) -> Option<(Op, usize)> {
+        let token = self.peek()?;
+        let op = match &token.kind {
+            Kind::Op(op @ (Op::Add | Op::Sub)) => *op,
+            Kind::Word(word) if word == "plus" => Op::Add,
+            Kind::Word(word) if word == "minus" => Op::Sub,
+            _ => return None,
+        };
+        let column = token.column;
+        self.next += 1;
+        Some((op, column))
+    }
+
+    /// `*`, `/`, times, multiplied by, divided by, over
+    fn multiplicative_op(&mut self) -> Result<Option<(Op, usize)>, ParseError> {
+        let Some(token) = self.peek() else {
+            return Ok(None);
+        };
+        let column = token.column;
+        let (op, needs_by) = match &token.kind {
+            Kind::Op(op @ (Op::Mul | Op::Div)) => (*op, false),
+            Kind::Word(word) if word == "times" => (Op::Mul, false),
+            Kind::Word(word) if word == "over" => (Op::Div, false),
+            Kind::Word(word) if word == "multiplied" => (Op::Mul, true),
+            Kind::Word(word) if word == "divided" => (Op::Div, true),
+            _ => return Ok(None),
+        };
+        self.next += 1;
+
+        if needs_by {
+            match self.advance() {
+                Some(Token {
+                    kind: Kind::Word(word),
+                    ..
+                }) if word == "by" => {}
+                Some(token) => return Err(self.unexpected(token)),
+                None => return Err(ParseError::UnexpectedEnd),
+            }
+        }
+
+        Ok(Some((op, column)))
+    }
+
+    fn expression(&mut self) -> Result<i128, ParseError> {
+        let mut value = self.term()?;
+        while let Some((op, column)) = self.additive_op() {
+            let right = self.term()?;
+            value = apply(op, value, right, column)?;
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<i128, ParseError> {
+        let mut value = self.unary()?;
+        while let Some((op, column)) = self.multiplicative_op()? {
+            let right = self.unary()?;
+            value = apply(op, value, right, column)?;
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<i128, ParseError> {
+        let Some(token) = self.peek().cloned() else {
+            return Err(ParseError::UnexpectedEnd);
+        };
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError::TooDeep {
+                column: token.column,
+            });
+        }
+
+        self.depth += 1;
+        let value = self.prefixed(token);
+        self.depth -= 1;
+        value
+    }
+
+    /// `unary` after the depth check.
+    fn prefixed(&mut self, token: Token) -> Result<i128, ParseError> {
+        match &token.kind {
+            Kind::Op(Op::Sub) => {
+                self.next += 1;
+                self.unary()?.checked_neg().ok_or(ParseError::Overflow)
+            }
+            Kind::Op(Op::Add) => {
+                self.next += 1;
+                self.unary()
+            }
+            Kind::Word(word) if word == "minus" || word == "negative" => {
+                self.next += 1;
+                self.unary()?.checked_neg().ok_or(ParseError::Overflow)
+            }
+            Kind::Word(word) if word == "half" => {
+                self.next += 1;
+                self.skip_word("of");
+                apply(Op::Div, self.unary()?, 2, token.column)
+            }
+            Kind::Word(word) if word == "double" || word == "twice" => {
+                self.next += 1;
+                self.skip_word("of");
+                apply(Op::Mul, self.unary()?, 2, token.column)
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn skip_word(&mut self, expected: &str) {
+        if self.peek_word() == Some(expected) {
+            self.next += 1;
+        }
+    }
+
+    fn primary(&mut self) -> Result<i128, ParseError> {
+        let Some(token) = self.advance() else {
+            return Err(ParseError::UnexpectedEnd);
+        };
+
+        match token.kind {
+            Kind::Number(number) => Ok(number),
+            Kind::Open => {
+                let value = self.expression()?;
+                match self.advance() {
+                    Some(Token {
+                        kind: Kind::Close, ..
+                    }) => Ok(value),
+                    Some(token) => Err(self.unexpected(token)),
+                    None => Err(ParseError::UnexpectedEnd),
+                }
+            }
+            Kind::Word(ref word) if is_number_word(word) => {
+                self.next -= 1;
+                self.number_words()
+            }
+            _ => Err(self.unexpected(token)),
+        }
+    }
+
+    /// A run like "one hundred and five" or "two thousand forty-two".
+    fn number_words(&mut self) -> Result<i128, ParseError> {
+        let mut total: i128 = 0;
+        let mut current: i128 = 0;
+        // The last word below a hundred, only a unit may follow a tens word as in forty two
+        let mut last_small: Option<i128> = None;
+        // Each scale must be smaller than the one before, and a group has at most one hundred
+        let mut last_scale: Option<i128> = None;
+        let mut hundred = false;
+
+        while let Some(word) = self.peek_word().map(String::from) {
+            // "and" only joins two number words, otherwise it ends the number
+            if word == "and" {
+                let joins = match self.tokens.get(self.next + 1) {
+                    Some(Token {
+                        kind: Kind::Word(next),
+                        ..
+                    }) => is_number_word(next),
+                    _ => false,
+                };
+                if !joins {
+                    break;
+                }
+                self.next += 1;
+                continue;
+            }
+            if !is_number_word(&word) {
+                break;
+            }
+            let token = self.advance().expect("peeked a word");
+            let unexpected = || ParseError::Unexpected {
+                token: token.text.clone(),
+                column: token.column,
+            };
+
+            for part in word.split('-') {
+                if let Some(value) = small_number(part) {
+                    let follows_tens = last_small.is_some_and(|last| last >= 20 && last % 10 == 0);
+                    if last_small.is_some() && !(follows_tens && (1..10).contains(&value)) {
+                        return Err(unexpected());
+                    }
+                    last_small = Some(value);
+                    current = current.checked_add(value).ok_or(ParseError::Overflow)?;
+                    continue;
+                }
+
+                last_small = None;
+                if let Some(100) = scale(part) {
+                    if hundred {
+                        return Err(unexpected());
+                    }
+                    hundred = true;
+                    current = current
+                        .max(1)
+                        .checked_mul(100)
+                        .ok_or(ParseError::Overflow)?;
+                } else if let Some(scale) = scale(part) {
+                    if last_scale.is_some_and(|last| scale >= last) {
+                        return Err(unexpected());
+                    }
+                    last_scale = Some(scale);
+                    hundred = false;
+                    let group = current
+                        .max(1)
+                        .checked_mul(scale)
+                        .ok_or(ParseError::Overflow)?;
+                    total = total.checked_add(group).ok_or(ParseError::Overflow)?;
+                    current = 0;
+                }
+            }
+        }
+
+        total.checked_add(current).ok_or(ParseError::Overflow)
+    }
+}
+
+fn is_known_word(word: &str) -> bool {
+    is_number_word(word)
+        || matches!(
+            word,
+            "plus"
+                | "minus"
+                | "times"
+                | "over"
+                | "multiplied"
+                | "divided"
+                | "by"
+                | "negative"
+                | "half"
+                | "double"
+                | "twice"
+                | "of"
+                | "and"
+        )
+}
+
+fn apply(op: Op, left: i128, right: i128, column: usize) -> Result<i128, ParseError> {
+    match op {
+        Op::Add => left.checked_add(right).ok_or(ParseError::Overflow),
+        Op::Sub => left.checked_sub(right).ok_or(ParseError::Overflow),
+        Op::Mul => left.checked_mul(right).ok_or(ParseError::Overflow),
+        Op::Div if right == 0 => Err(ParseError::DivisionByZero { column }),
+        // i128::MIN / -1 does not fit, and neither does its remainder
+        Op::Div => match left.checked_rem(right) {
+            Some(0) => left.checked_div(right).ok_or(ParseError::Overflow),
+            Some(_) => Err(ParseError::NotWhole { column }),
+            None => Err(ParseError::Overflow),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dividing_the_smallest_number_by_minus_one_overflows() {
+        assert_eq!(
+            parse_guess("(-170141183460469231731687303715884105727 - 1) / -1"),
+            Err(ParseError::Overflow)
+        );
+        assert_eq!(
+            parse_guess("(-170141183460469231731687303715884105727 - 1) / 1"),
+            Ok(i128::MIN)
+        );
+        assert_eq!(
+            parse_guess("7 / 2"),
+            Err(ParseError::NotWhole { column: 3 })
+        );
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error_not_a_stack_overflow() {
+        let nested = format!(
+            "{}1{}",
+            "(".repeat(MAX_DEPTH - 1),
+            ")".repeat(MAX_DEPTH - 1)
+        );
+        assert_eq!(parse_guess(&nested), Ok(1));
+
+        let brackets = "(".repeat(200_000);
+        assert_eq!(
+            parse_guess(&brackets),
+            Err(ParseError::TooDeep {
+                column: MAX_DEPTH + 1
+            })
+        );
+        let minuses = "minus ".repeat(200_000) + "1";
+        assert!(matches!(
+            parse_guess(&minuses),
+            Err(ParseError::TooDeep { .. })
+        ));
+    }
+
+    #[test]
+    fn number_words_do_not_add_up_side_by_side() {
+        assert_eq!(parse_guess("forty two"), Ok(42));
+        assert_eq!(parse_guess("forty-two"), Ok(42));
+        assert_eq!(parse_guess("one hundred and five"), Ok(105));
+        assert_eq!(parse_guess("two thousand forty-two"), Ok(2042));
+        assert_eq!(parse_guess("nineteen hundred twelve"), Ok(1912));
+        assert_eq!(
+            parse_guess("two hundred thousand three hundred one"),
+            Ok(200_301)
+        );
+        assert_eq!(parse_guess("one million two thousand"), Ok(1_002_000));
+
+        for (input, token, column) in [
+            ("one two", "two", 5),
+            ("twenty thirty", "thirty", 8),
+            ("twelve three", "three", 8),
+            ("forty twelve", "twelve", 7),
+            ("twenty-thirty", "twenty-thirty", 1),
+            ("forty two one", "one", 11),
+            ("thousand thousand", "thousand", 10),
+            ("one thousand two thousand", "thousand", 18),
+            ("one thousand one million", "million", 18),
+            ("five hundred hundred", "hundred", 14),
+        ] {
+            assert_eq!(
+                parse_guess(input),
+                Err(ParseError::Unexpected {
+                    token: token.to_string(),
+                    column
+                }),
+                "{input}"
+            );
+        }
+    }
+}
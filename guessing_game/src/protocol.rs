@@ -0,0 +1,225 @@
+/*
+ * JSON lines protocol for bots and test harnesses, one JSON object per line in both directions.
+ *
+ *   -> {"type":"start","difficulty":"medium","min":1,"max":100,"max_attempts":null,"commitment":"..."}
+ *   -> {"type":"prompt","attempt":1,"attempts_left":null}
+ *   <- {"guess":42}
+ *   -> {"type":"result","result":"too_small","guess":42,"attempt":1}
+ *   -> {"type":"error","message":"..."}      (the line was not a valid request, no attempt used)
+ *   -> {"type":"reveal","secret":42,"salt":"...","commitment":"..."}
+ *
+ * The round ends after a result of "won" or "lost", a lost result also carries the secret.
+ * The reveal comes last, also when the input ends early, so the commitment can be checked.
+ */
+
+use crate::{
+    fair::Seal,
+    game::{Game, Outcome},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::Ordering,
+    io::{self, BufRead, Write},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Request {
+    pub guess: i128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuessResult {
+    TooSmall,
+    TooBig,
+    Won,
+    Lost,
+}
+
+impl GuessResult {
+    /// `None` for `Outcome::Invalid`, that guess never happened.
+    pub fn from_outcome(outcome: Outcome) -> Option<Self> {
+        match outcome {
+            Outcome::TooSmall => Some(GuessResult::TooSmall),
+            Outcome::TooBig => Some(GuessResult::TooBig),
+            Outcome::Won { .. } => Some(GuessResult::Won),
+            Outcome::Lost { .. } => Some(GuessResult::Lost),
+            Outcome::Invalid => None,
+        }
+    }
+}
+
+impl From<Ordering> for GuessResult {
+    fn from(ordering: Ordering) -> Self {
+        match ordering {
+            Ordering::Less => GuessResult::TooSmall,
+            Ordering::Greater => GuessResult::TooBig,
+            Ordering::Equal => GuessResult::Won,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    Start {
+        difficulty: String,
+        min: i128,
+        max: i128,
+        max_attempts: Option<u32>,
+        commitment: String,
+    },
+    Prompt {
+        attempt: u32,
+        attempts_left: Option<u32>,
+    },
+    Result {
+        result: GuessResult,
+        guess: i128,
+        attempt: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        secret: Option<i128>,
+    },
+    Error {
+        message: String,
+    },
+    Reveal {
+        secret: i128,
+        salt: String,
+        commitment: String,
+    },
+}
+
+fn write_message(output: &mut impl Write, message: &Message) -> io::Result<()> {
+    serde_json::to_writer(&mut *output, message)?;
+    writeln!(output)?;
+    // Bots wait for every line, so nothing may sit in a buffer
+    output.flush()
+}
+
+/// Plays the current round of `game` over the protocol, `seal` must hold the secret of the round.
+/// Returns the final outcome, or `None` if the input ended before the round did.
+pub fn run(
+    game: &mut Game,
+    seal: &Seal,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<Option<Outcome>> {
+    let config = *game.config();
+    write_message(
+        &mut output,
+        &Message::Start {
+            difficulty: config.difficulty().to_string(),
+            min: config.min(),
+            max: config.max(),
+            max_attempts: config.max_attempts(),
+            commitment: seal.commitment(),
+        },
+    )?;
+
+    let mut lines = input.lines();
+
+    let outcome = loop {
+        write_message(
+            &mut output,
+            &Message::Prompt {
+                attempt: game.attempts() + 1,
+                attempts_left: game.attempts_left(),
+            },
+        )?;
+
+        let Some(line) = lines.next().transpose()? else {
+            break None;
+        };
+
+        let request: Request = match serde_json::from_str(&line) {
+            Ok(request) => request,
+            Err(e) => {
+                write_message(
+                    &mut output,
+                    &Message::Error {
+                        message: format!("expected {{\"guess\": <integer>}}: {e}"),
+                    },
+                )?;
+                continue;
+            }
+        };
+
+        let outcome = game.check(request.guess);
+        // `check` only refuses guesses once the round is over, and we stop before that
+        let result = GuessResult::from_outcome(outcome).expect("the round is already over");
+
+        write_message(
+            &mut output,
+            &Message::Result {
+                result,
+                guess: request.guess,
+                attempt: game.attempts(),
+                secret: match outcome {
+                    Outcome::Lost { secret } => Some(secret),
+                    _ => None,
+                },
+            },
+        )?;
+
+        if game.is_over() {
+            break Some(outcome);
+        }
+    };
+
+    write_message(
+        &mut output,
+        &Message::Reveal {
+            secret: seal.secret(),
+            salt: seal.salt().to_string(),
+            commitment: seal.commitment(),
+        },
+    )?;
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, fair};
+    use serde_json::Value;
+
+    fn play(input: &str) -> (Option<Outcome>, Vec<Value>) {
+        let mut game = Game::with_secret(Config::default(), 42);
+        let seal = Seal::new(42);
+        let mut output = Vec::new();
+        let outcome = run(&mut game, &seal, input.as_bytes(), &mut output).unwrap();
+        let messages = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        (outcome, messages)
+    }
+
+    #[test]
+    fn the_commitment_comes_first_and_the_reveal_last() {
+        for (input, outcome) in [
+            (
+                "{\"guess\":10}\n{\"guess\":42}\n",
+                Some(Outcome::Won { attempts: 2 }),
+            ),
+            ("{\"guess\":10}\n", None),
+        ] {
+            let (played, messages) = play(input);
+            assert_eq!(played, outcome);
+
+            let start = messages.first().unwrap();
+            let reveal = messages.last().unwrap();
+            assert_eq!(start["type"], "start");
+            assert_eq!(reveal["type"], "reveal");
+            assert_eq!(reveal["secret"], 42);
+            assert_eq!(reveal["commitment"], start["commitment"]);
+            assert!(fair::verify(
+                start["commitment"].as_str().unwrap(),
+                42,
+                reveal["salt"].as_str().unwrap()
+            ));
+        }
+    }
+}
@@ -0,0 +1,161 @@
+/*
+ * Reverse mode, the player thinks of a number and the computer bisects it.
+ * Every answer is kept, so when the answers stop leaving any possible number
+ * we can tell the player which two answers disagree.
+ */
+
+use crate::{config::Config, strategy::Interval};
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+/// The player's reply to "Is it N?".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    /// The player's number is higher than the guess.
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl Answer {
+    /// The same feedback the normal game gives, `guess.cmp(&secret)`.
+    pub fn ordering(&self) -> Ordering {
+        match self {
+            Answer::Higher => Ordering::Less,
+            Answer::Lower => Ordering::Greater,
+            Answer::Correct => Ordering::Equal,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Answer::Higher => "higher",
+            Answer::Lower => "lower",
+            Answer::Correct => "correct",
+        })
+    }
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "higher" | "h" | "+" | ">" => Ok(Answer::Higher),
+            "lower" | "l" | "-" | "<" => Ok(Answer::Lower),
+            "correct" | "c" | "yes" | "y" | "=" => Ok(Answer::Correct),
+            other => Err(format!(
+                "`{other}` is not an answer, say higher, lower or correct."
+            )),
+        }
+    }
+}
+
+/// The answers so far leave no number possible.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contradiction {
+    /// 1-based number of the answer that emptied the interval, with its guess.
+    pub answer: (usize, i128, Answer),
+    /// The earlier answer it disagrees with, `None` when it contradicts the range itself.
+    pub conflicts_with: Option<(usize, i128, Answer)>,
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (number, guess, answer) = self.answer;
+        write!(f, "answer #{number} ({answer} than {guess}) ")?;
+        match self.conflicts_with {
+            Some((number, guess, answer)) => {
+                write!(f, "contradicts answer #{number} ({answer} than {guess})")
+            }
+            None => write!(f, "leaves nothing inside the range"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// Ask the player about this number next.
+    Guess(i128),
+    Found {
+        number: i128,
+        attempts: u32,
+    },
+}
+
+#[derive(Debug)]
+pub struct Reverse {
+    interval: Interval,
+    guess: i128,
+    history: Vec<(i128, Answer)>,
+    /// Index into `history` of the answers that set the current low and high bounds.
+    low_from: Option<usize>,
+    high_from: Option<usize>,
+}
+
+impl Reverse {
+    pub fn new(config: &Config) -> Self {
+        let interval = Interval::from_config(config);
+        Self {
+            interval,
+            guess: interval.midpoint(),
+            history: Vec::new(),
+            low_from: None,
+            high_from: None,
+        }
+    }
+
+    /// The number the player is being asked about.
+    pub fn guess(&self) -> i128 {
+        self.guess
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    pub fn history(&self) -> &[(i128, Answer)] {
+        &self.history
+    }
+
+    /// Applies the player's answer about the current guess.
+    pub fn answer(&mut self, answer: Answer) -> Result<Step, Contradiction> {
+        let index = self.history.len();
+        self.history.push((self.guess, answer));
+        let entry = |i: usize| (i + 1, self.history[i].0, self.history[i].1);
+
+        let before = self.interval;
+        self.interval.narrow(self.guess, answer.ordering());
+
+        if self.interval.is_empty() {
+            let conflicts_with = match answer {
+                Answer::Higher => self.high_from,
+                Answer::Lower => self.low_from,
+                // Correct on a guess we made is always consistent
+                Answer::Correct => None,
+            };
+            return Err(Contradiction {
+                answer: entry(index),
+                conflicts_with: conflicts_with.map(entry),
+            });
+        }
+
+        if self.interval.low != before.low {
+            self.low_from = Some(index);
+        }
+        if self.interval.high != before.high {
+            self.high_from = Some(index);
+        }
+
+        if answer == Answer::Correct {
+            return Ok(Step::Found {
+                number: self.guess,
+                attempts: self.history.len() as u32,
+            });
+        }
+
+        self.guess = self.interval.midpoint();
+        Ok(Step::Guess(self.guess))
+    }
+}
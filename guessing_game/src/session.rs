@@ -0,0 +1,144 @@
+/*
+ * Saving an unfinished round so it can be picked up again with `--resume`.
+ * The state is a small JSON file next to the leaderboard.
+ */
+
+use crate::{
+    config::{Config, Difficulty},
+    game::Game,
+    leaderboard,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedRound {
+    pub difficulty: Difficulty,
+    pub min: i128,
+    pub max: i128,
+    pub max_attempts: Option<u32>,
+    pub secret: i128,
+    pub guesses: Vec<i128>,
+    pub name: Option<String>,
+    pub elapsed_ms: u64,
+    /// Keeps the commitment shown at the start valid, missing in files saved before it existed.
+    #[serde(default)]
+    pub salt: Option<String>,
+}
+
+impl SavedRound {
+    pub fn from_game(game: &Game, salt: &str, name: Option<String>, elapsed: Duration) -> Self {
+        let config = game.config();
+        Self {
+            difficulty: config.difficulty(),
+            min: config.min(),
+            max: config.max(),
+            max_attempts: config.max_attempts(),
+            secret: game.secret(),
+            guesses: game.guesses().to_vec(),
+            name,
+            elapsed_ms: elapsed.as_millis() as u64,
+            salt: Some(salt.to_string()),
+        }
+    }
+
+    /// Rebuilds the game, fails if the file was edited into something that is not a valid round.
+    pub fn to_game(&self) -> io::Result<Game> {
+        let config = Config::new(self.difficulty, self.min, self.max, self.max_attempts)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if !config.contains(self.secret) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the saved secret is outside the saved range",
+            ));
+        }
+
+        Ok(Game::resume(config, self.secret, &self.guesses))
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_millis(self.elapsed_ms)
+    }
+
+    /// `$XDG_DATA_HOME/guessing_game/session.json`
+    pub fn default_path() -> Option<PathBuf> {
+        Some(leaderboard::data_dir()?.join("session.json"))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write then rename, so a crash never leaves half a state file behind
+        let temporary = path.with_extension("json.tmp");
+        fs::write(&temporary, serde_json::to_vec_pretty(self)?)?;
+        fs::rename(temporary, path)
+    }
+
+    /// Loads the saved round and rebuilds its game, then removes the file so a round can only be
+    /// resumed once. A file that does not hold a valid round is left alone, one whose round is
+    /// already over is removed too since there is nothing left to play.
+    pub fn take(path: &Path) -> io::Result<(Self, Game)> {
+        let saved: Self = serde_json::from_slice(&fs::read(path)?)?;
+        let game = saved.to_game()?;
+        fs::remove_file(path)?;
+
+        if game.is_over() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the saved round was already over, it has been removed",
+            ));
+        }
+        Ok((saved, game))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_valid_round_is_taken_and_removed() {
+        let path = std::env::temp_dir().join(format!("session-{}.json", std::process::id()));
+        let config = Config::new(Difficulty::Custom, 1, 100, Some(5)).unwrap();
+        let game = Game::resume(config, 42, &[50, 25]);
+        let mut saved = SavedRound::from_game(&game, "salt", None, Duration::from_secs(3));
+
+        saved.secret = 500;
+        saved.save(&path).unwrap();
+        assert_eq!(
+            SavedRound::take(&path).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(path.exists(), "a broken round must not be thrown away");
+
+        saved.secret = 42;
+        saved.save(&path).unwrap();
+        let (taken, resumed) = SavedRound::take(&path).unwrap();
+        assert_eq!(taken, saved);
+        assert_eq!(resumed.guesses(), [50, 25]);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn a_round_that_is_already_over_is_not_resumed() {
+        let path = std::env::temp_dir().join(format!("session-over-{}.json", std::process::id()));
+        let config = Config::new(Difficulty::Custom, 1, 100, Some(3)).unwrap();
+
+        for guesses in [&[50, 25, 42][..], &[1, 2, 3], &[1, 2, 3, 4]] {
+            let game = Game::resume(config, 42, &[]);
+            let mut saved = SavedRound::from_game(&game, "salt", None, Duration::ZERO);
+            saved.guesses = guesses.to_vec();
+            saved.save(&path).unwrap();
+
+            let error = SavedRound::take(&path).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{guesses:?}");
+            assert!(!path.exists(), "{guesses:?}");
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*
+ * Simulation mode, many rounds of one strategy against seeded games.
+ */
+
+use crate::{
+    config::Config,
+    game::{Game, Host},
+    strategy::{self, Strategy},
+};
+use std::{collections::BTreeMap, fmt};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub strategy: &'static str,
+    pub range_size: u128,
+    /// Attempts of every won round, in the order they were played.
+    pub attempts: Vec<u32>,
+    /// Rounds where the attempt limit ran out.
+    pub lost: u32,
+}
+
+impl Report {
+    pub fn rounds(&self) -> usize {
+        self.attempts.len() + self.lost as usize
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.attempts.is_empty() {
+            return None;
+        }
+
+        let total: u64 = self.attempts.iter().map(|&a| u64::from(a)).sum();
+        Some(total as f64 / self.attempts.len() as f64)
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        let mut sorted = self.attempts.clone();
+        sorted.sort_unstable();
+
+        let middle = sorted.len() / 2;
+        match sorted.len() {
+            0 => None,
+            len if len % 2 == 0 => Some(f64::from(sorted[middle - 1] + sorted[middle]) / 2.0),
+            _ => Some(f64::from(sorted[middle])),
+        }
+    }
+
+    pub fn worst(&self) -> Option<u32> {
+        self.attempts.iter().copied().max()
+    }
+
+    /// Amount of rounds won with each attempt count.
+    pub fn histogram(&self) -> BTreeMap<u32, usize> {
+        let mut histogram = BTreeMap::new();
+        for &attempts in &self.attempts {
+            *histogram.entry(attempts).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    pub fn optimal_worst_case(&self) -> u32 {
+        strategy::optimal_worst_case(self.range_size)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Strategy {} over {} rounds ({} numbers)",
+            self.strategy,
+            self.rounds(),
+            self.range_size
+        )?;
+
+        match (self.mean(), self.median(), self.worst()) {
+            (Some(mean), Some(median), Some(worst)) => writeln!(
+                f,
+                "mean {mean:.2}, median {median:.1}, worst {worst} (optimal worst case {})",
+                self.optimal_worst_case()
+            )?,
+            _ => writeln!(f, "no rounds won")?,
+        }
+        if self.lost > 0 {
+            writeln!(f, "lost {} rounds to the attempt limit", self.lost)?;
+        }
+
+        write!(f, "{}", Histogram(&self.histogram()))
+    }
+}
+
+/// How many rounds took each number of attempts, one bar per line.
+pub struct Histogram<'a>(pub &'a BTreeMap<u32, usize>);
+
+impl fmt::Display for Histogram<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tallest = self.0.values().copied().max().unwrap_or(0);
+        for (attempts, count) in self.0 {
+            // Bars are scaled so the tallest one is 50 characters
+            let width = (count * 50).div_ceil(tallest);
+            writeln!(f, "{attempts:>4} | {:<50} {count}", "#".repeat(width))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Plays `rounds` rounds, the secrets come from `seed` so runs are reproducible.
+pub fn simulate(strategy: &mut dyn Strategy, config: Config, rounds: u32, seed: u64) -> Report {
+    simulate_against(strategy, &mut Game::with_seed(config, seed), rounds)
+}
+
+/// Plays `rounds` rounds against any host, for example the evil one.
+pub fn simulate_against(strategy: &mut dyn Strategy, host: &mut impl Host, rounds: u32) -> Report {
+    let mut report = Report {
+        strategy: strategy.name(),
+        range_size: host.config().range_size(),
+        attempts: Vec::with_capacity(rounds as usize),
+        lost: 0,
+    };
+
+    for _ in 0..rounds {
+        match strategy::play_round(strategy, host) {
+            Some(attempts) => report.attempts.push(attempts),
+            None => report.lost += 1,
+        }
+        host.next_round();
+    }
+
+    report
+}
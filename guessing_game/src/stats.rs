@@ -0,0 +1,231 @@
+/*
+ * Per-player history of every finished round, won or lost, and the statistics built from it.
+ * Stored like the leaderboard, one tab separated line per round in `history.tsv`, appended
+ * under an exclusive lock.
+ */
+
+use crate::{
+    config::{Config, Difficulty},
+    leaderboard,
+    simulate::Histogram,
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayedRound {
+    pub name: String,
+    /// Unix time in seconds when the round ended.
+    pub finished: u64,
+    pub difficulty: Difficulty,
+    pub min: i128,
+    pub max: i128,
+    pub attempts: u32,
+    pub won: bool,
+    pub elapsed: Duration,
+}
+
+impl PlayedRound {
+    /// A round that just ended.
+    pub fn now(name: &str, config: &Config, attempts: u32, won: bool, elapsed: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            finished: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs()),
+            difficulty: config.difficulty(),
+            min: config.min(),
+            max: config.max(),
+            attempts,
+            won,
+            elapsed,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            leaderboard::clean_name(&self.name),
+            self.finished,
+            self.difficulty,
+            self.min,
+            self.max,
+            self.attempts,
+            if self.won { "won" } else { "lost" },
+            self.elapsed.as_millis()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let round = Self {
+            name: fields.next()?.to_string(),
+            finished: fields.next()?.parse().ok()?,
+            difficulty: fields.next()?.parse().ok()?,
+            min: fields.next()?.parse().ok()?,
+            max: fields.next()?.parse().ok()?,
+            attempts: fields.next()?.parse().ok()?,
+            won: match fields.next()? {
+                "won" => true,
+                "lost" => false,
+                _ => return None,
+            },
+            elapsed: Duration::from_millis(fields.next()?.parse().ok()?),
+        };
+
+        fields.next().is_none().then_some(round)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `$XDG_DATA_HOME/guessing_game/history.tsv`
+    pub fn default_path() -> Option<PathBuf> {
+        Some(leaderboard::data_dir()?.join("history.tsv"))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn record(&self, round: &PlayedRound) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.lock()?;
+        file.write_all(round.to_line().as_bytes())?;
+        file.flush()
+    }
+
+    /// Every readable round of `name`, oldest first. Lines that fail to parse are skipped.
+    pub fn rounds(&self, name: &str) -> io::Result<Vec<PlayedRound>> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        file.lock_shared()?;
+
+        let name = leaderboard::clean_name(name);
+        let mut rounds = Vec::new();
+        for line in BufReader::new(&file).lines() {
+            match PlayedRound::from_line(&line?) {
+                Some(round) if round.name == name => rounds.push(round),
+                _ => {}
+            }
+        }
+
+        rounds.sort_by_key(|round| round.finished);
+        Ok(rounds)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub name: String,
+    pub played: usize,
+    pub won: usize,
+    /// Won rounds in a row up to the last one.
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    /// Mean attempts of the won rounds and how many there were, per difficulty.
+    pub average_attempts: BTreeMap<Difficulty, (f64, usize)>,
+    /// How many rounds were won in how many attempts.
+    pub distribution: BTreeMap<u32, usize>,
+}
+
+impl Stats {
+    /// `rounds` oldest first, as `History::rounds` returns them.
+    pub fn new(name: &str, rounds: &[PlayedRound]) -> Self {
+        let mut longest_streak = 0;
+        let mut streak = 0;
+        let mut totals: BTreeMap<Difficulty, (u64, usize)> = BTreeMap::new();
+        let mut distribution = BTreeMap::new();
+
+        for round in rounds {
+            if round.won {
+                streak += 1;
+                longest_streak = longest_streak.max(streak);
+
+                let total = totals.entry(round.difficulty).or_default();
+                total.0 += u64::from(round.attempts);
+                total.1 += 1;
+                *distribution.entry(round.attempts).or_default() += 1;
+            } else {
+                streak = 0;
+            }
+        }
+
+        Self {
+            name: name.to_string(),
+            played: rounds.len(),
+            won: rounds.iter().filter(|round| round.won).count(),
+            current_streak: streak,
+            longest_streak,
+            average_attempts: totals
+                .into_iter()
+                .map(|(difficulty, (attempts, won))| {
+                    (difficulty, (attempts as f64 / won as f64, won))
+                })
+                .collect(),
+            distribution,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Statistics for {}", self.name)?;
+        if self.played == 0 {
+            return writeln!(f, "no finished rounds yet");
+        }
+
+        writeln!(
+            f,
+            "played {}, won {} ({:.0}%)",
+            self.played,
+            self.won,
+            100.0 * self.won as f64 / self.played as f64
+        )?;
+        writeln!(
+            f,
+            "current streak {}, longest streak {}",
+            self.current_streak, self.longest_streak
+        )?;
+
+        if !self.average_attempts.is_empty() {
+            writeln!(f, "average attempts")?;
+            for (difficulty, (average, won)) in &self.average_attempts {
+                writeln!(
+                    f,
+                    "  {:<7} {average:>6.2} over {won} won",
+                    difficulty.as_str()
+                )?;
+            }
+        }
+
+        if !self.distribution.is_empty() {
+            writeln!(f, "guess distribution")?;
+        }
+        write!(f, "{}", Histogram(&self.distribution))
+    }
+}
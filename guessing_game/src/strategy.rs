@@ -0,0 +1,291 @@
+/*
+ * Automated players. They play through `Game::check`, the same comparison the binary uses,
+ * and only ever learn the `Ordering` of their guess against the secret.
+ */
+
+use crate::{
+    config::Config,
+    game::{Host, Outcome},
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cmp::Ordering;
+
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+
+    /// Called before every round with the range the secret is in.
+    fn reset(&mut self, config: &Config);
+
+    fn next_guess(&mut self) -> i128;
+
+    /// `ordering` is `guess.cmp(&secret)`, so `Less` means the guess was too small.
+    fn feedback(&mut self, guess: i128, ordering: Ordering);
+}
+
+/// The numbers that are still possible after the feedback so far, both ends inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval {
+    pub low: i128,
+    pub high: i128,
+}
+
+impl Interval {
+    pub fn new(low: i128, high: i128) -> Self {
+        Self { low, high }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.min(), config.max())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.low > self.high
+    }
+
+    /// Amount of numbers left, 0 once the feedback contradicts itself.
+    pub fn len(&self) -> u128 {
+        if self.is_empty() {
+            0
+        } else {
+            self.high.abs_diff(self.low).saturating_add(1)
+        }
+    }
+
+    pub fn contains(&self, number: i128) -> bool {
+        (self.low..=self.high).contains(&number)
+    }
+
+    /// Lower middle, written so it cannot overflow even for the full `i128` range.
+    pub fn midpoint(&self) -> i128 {
+        self.low + (self.high.abs_diff(self.low) / 2) as i128
+    }
+
+    pub fn narrow(&mut self, guess: i128, ordering: Ordering) {
+        match ordering {
+            Ordering::Less => self.low = self.low.max(guess.saturating_add(1)),
+            Ordering::Greater => self.high = self.high.min(guess.saturating_sub(1)),
+            Ordering::Equal => {
+                self.low = guess;
+                self.high = guess;
+            }
+        }
+    }
+}
+
+/// Always guesses the middle of what is left, the optimal strategy.
+#[derive(Debug)]
+pub struct BinarySearch {
+    interval: Interval,
+}
+
+impl BinarySearch {
+    pub fn new() -> Self {
+        Self {
+            interval: Interval::new(0, 0),
+        }
+    }
+}
+
+impl Default for BinarySearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for BinarySearch {
+    fn name(&self) -> &'static str {
+        "binary"
+    }
+
+    fn reset(&mut self, config: &Config) {
+        self.interval = Interval::from_config(config);
+    }
+
+    fn next_guess(&mut self) -> i128 {
+        self.interval.midpoint()
+    }
+
+    fn feedback(&mut self, guess: i128, ordering: Ordering) {
+        self.interval.narrow(guess, ordering);
+    }
+}
+
+/// Guesses any number that is still possible.
+#[derive(Debug)]
+pub struct RandomGuess {
+    rng: ChaCha8Rng,
+    interval: Interval,
+}
+
+impl RandomGuess {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            interval: Interval::new(0, 0),
+        }
+    }
+}
+
+impl Strategy for RandomGuess {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn reset(&mut self, config: &Config) {
+        self.interval = Interval::from_config(config);
+    }
+
+    fn next_guess(&mut self) -> i128 {
+        self.rng.gen_range(self.interval.low..=self.interval.high)
+    }
+
+    fn feedback(&mut self, guess: i128, ordering: Ordering) {
+        self.interval.narrow(guess, ordering);
+    }
+}
+
+/// Counts up from the bottom of the range, the worst sensible strategy.
+#[derive(Debug)]
+pub struct LinearScan {
+    next: i128,
+}
+
+impl LinearScan {
+    pub fn new() -> Self {
+        Self { next: 0 }
+    }
+}
+
+impl Default for LinearScan {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for LinearScan {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+
+    fn reset(&mut self, config: &Config) {
+        self.next = config.min();
+    }
+
+    fn next_guess(&mut self) -> i128 {
+        self.next
+    }
+
+    fn feedback(&mut self, guess: i128, _ordering: Ordering) {
+        self.next = guess.saturating_add(1);
+    }
+}
+
+/// Bisects like a person does, aiming for the middle but missing it by up to a quarter of the interval.
+#[derive(Debug)]
+pub struct HumanLike {
+    rng: ChaCha8Rng,
+    interval: Interval,
+}
+
+impl HumanLike {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            interval: Interval::new(0, 0),
+        }
+    }
+}
+
+impl Strategy for HumanLike {
+    fn name(&self) -> &'static str {
+        "human"
+    }
+
+    fn reset(&mut self, config: &Config) {
+        self.interval = Interval::from_config(config);
+    }
+
+    fn next_guess(&mut self) -> i128 {
+        let middle = self.interval.midpoint();
+        let spread = (self.interval.len() / 4).min(i128::MAX as u128) as i128;
+        let noise = self.rng.gen_range(-spread..=spread);
+
+        middle
+            .saturating_add(noise)
+            .clamp(self.interval.low, self.interval.high)
+    }
+
+    fn feedback(&mut self, guess: i128, ordering: Ordering) {
+        self.interval.narrow(guess, ordering);
+    }
+}
+
+pub const STRATEGY_NAMES: [&str; 4] = ["binary", "random", "linear", "human"];
+
+/// Builds a strategy from its command line name, `seed` feeds the ones that use randomness.
+pub fn by_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    match name {
+        "binary" => Some(Box::new(BinarySearch::new())),
+        "random" => Some(Box::new(RandomGuess::new(seed))),
+        "linear" => Some(Box::new(LinearScan::new())),
+        "human" => Some(Box::new(HumanLike::new(seed))),
+        _ => None,
+    }
+}
+
+/// Plays the current round of `host` to the end, `None` if the attempt limit ran out.
+pub fn play_round(strategy: &mut dyn Strategy, host: &mut impl Host) -> Option<u32> {
+    strategy.reset(host.config());
+
+    loop {
+        let guess = strategy.next_guess();
+        let ordering = match host.check(guess) {
+            Outcome::TooSmall => Ordering::Less,
+            Outcome::TooBig => Ordering::Greater,
+            Outcome::Won { attempts } => return Some(attempts),
+            Outcome::Lost { .. } | Outcome::Invalid => return None,
+        };
+        strategy.feedback(guess, ordering);
+    }
+}
+
+/// The fewest guesses that always find the secret, `ceil(log2(range_size + 1))`.
+///
+/// Binary search reaches it, and no strategy can do better in the worst case
+/// because every guess splits what is left into at most three parts, one of them a single number.
+pub fn optimal_worst_case(range_size: u128) -> u32 {
+    // ceil(log2(n + 1)) is the bit length of n
+    u128::BITS - range_size.leading_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Difficulty, simulate::simulate};
+
+    #[test]
+    fn binary_search_never_needs_more_than_the_optimal_worst_case() {
+        let ranges = [
+            (1, 1),
+            (1, 2),
+            (1, 100),
+            (-1000, -1),
+            (-50, 50),
+            (0, u64::MAX as i128),
+            (i64::MIN as i128, i64::MAX as i128),
+        ];
+        for (min, max) in ranges {
+            let config = Config::new(Difficulty::Custom, min, max, None).unwrap();
+            for seed in 0..5 {
+                let report = simulate(&mut BinarySearch::new(), config, 200, seed);
+                assert_eq!(report.lost, 0, "{min}..={max}");
+                assert!(
+                    report.worst().unwrap() <= optimal_worst_case(config.range_size()),
+                    "{min}..={max} took {:?}",
+                    report.worst()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,348 @@
+/*
+ * Time attack sessions, several rounds against a clock per round or per session, with a score for each round.
+ * The clock is a trait so tests can move time forward by hand instead of sleeping.
+ */
+
+use crate::{
+    config::Config,
+    game::{Game, Outcome},
+    strategy,
+};
+use std::{
+    cell::Cell,
+    fmt,
+    time::{Duration, Instant},
+};
+
+pub trait Clock {
+    /// Time since the clock started.
+    fn elapsed(&self) -> Duration;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    started: Instant,
+}
+
+impl SystemClock {
+    pub fn start() -> Self {
+        Self {
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// A clock that only moves when told to.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn elapsed(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn elapsed(&self) -> Duration {
+        (**self).elapsed()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeLimit {
+    PerRound(Duration),
+    PerSession(Duration),
+}
+
+/// Points for one won round.
+///
+/// `100 * log2(range size)` for the size of the range, times `optimal / attempts` (capped at 1)
+/// for playing like binary search, times `60 / (60 + seconds)` so a minute halves the score.
+pub fn score(range_size: u128, attempts: u32, elapsed: Duration) -> u64 {
+    let bits = (range_size as f64).log2().max(1.0);
+    let optimal = strategy::optimal_worst_case(range_size) as f64;
+    let attempt_factor = (optimal / f64::from(attempts.max(1))).min(1.0);
+    let time_factor = 60.0 / (60.0 + elapsed.as_secs_f64());
+
+    (100.0 * bits * attempt_factor * time_factor).round() as u64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundEnd {
+    Won,
+    /// The attempt limit ran out.
+    Lost,
+    TimedOut,
+}
+
+impl fmt::Display for RoundEnd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RoundEnd::Won => "won",
+            RoundEnd::Lost => "lost",
+            RoundEnd::TimedOut => "time up",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RoundResult {
+    /// 1-based
+    pub round: u32,
+    pub secret: i128,
+    pub attempts: u32,
+    pub elapsed: Duration,
+    pub end: RoundEnd,
+    /// 0 unless the round was won.
+    pub score: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// The round goes on, `TooSmall` or `TooBig`.
+    Feedback(Outcome),
+    RoundOver(RoundResult),
+}
+
+pub struct TimedSession<C: Clock> {
+    clock: C,
+    limit: Option<TimeLimit>,
+    rounds: u32,
+    game: Game,
+    round_started: Duration,
+    results: Vec<RoundResult>,
+    finished: bool,
+}
+
+impl<C: Clock> TimedSession<C> {
+    pub fn new(game: Game, rounds: u32, limit: Option<TimeLimit>, clock: C) -> Self {
+        Self {
+            round_started: clock.elapsed(),
+            clock,
+            limit,
+            rounds,
+            game,
+            results: Vec::new(),
+            finished: rounds == 0,
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn config(&self) -> &Config {
+        self.game.config()
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// 1-based number of the round being played.
+    pub fn round(&self) -> u32 {
+        self.results.len() as u32 + 1
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn results(&self) -> &[RoundResult] {
+        &self.results
+    }
+
+    pub fn total_score(&self) -> u64 {
+        self.results.iter().map(|result| result.score).sum()
+    }
+
+    /// `None` when there is no time limit.
+    pub fn time_left(&self) -> Option<Duration> {
+        let now = self.clock.elapsed();
+        let (limit, since) = match self.limit? {
+            TimeLimit::PerRound(limit) => (limit, self.round_started),
+            // The session clock starts with the first round
+            TimeLimit::PerSession(limit) => (limit, Duration::ZERO),
+        };
+
+        Some(limit.saturating_sub(now.saturating_sub(since)))
+    }
+
+    fn time_is_up(&self) -> bool {
+        self.time_left() == Some(Duration::ZERO)
+    }
+
+    /// Ends the round if its time ran out, call it when waiting for input timed out.
+    pub fn expire(&mut self) -> Option<RoundResult> {
+        if self.finished || !self.time_is_up() {
+            return None;
+        }
+        Some(self.close_round(RoundEnd::TimedOut))
+    }
+
+    /// A guess that arrives after the time ran out is not counted, the round just ends.
+    pub fn guess(&mut self, guess: i128) -> Progress {
+        if let Some(result) = self.expire() {
+            return Progress::RoundOver(result);
+        }
+
+        match self.game.check(guess) {
+            Outcome::Won { .. } => Progress::RoundOver(self.close_round(RoundEnd::Won)),
+            Outcome::Lost { .. } => Progress::RoundOver(self.close_round(RoundEnd::Lost)),
+            outcome => Progress::Feedback(outcome),
+        }
+    }
+
+    fn close_round(&mut self, end: RoundEnd) -> RoundResult {
+        let now = self.clock.elapsed();
+        let elapsed = now.saturating_sub(self.round_started);
+        let attempts = self.game.attempts();
+
+        let result = RoundResult {
+            round: self.round(),
+            secret: self.game.secret(),
+            attempts,
+            elapsed,
+            end,
+            score: match end {
+                RoundEnd::Won => score(self.config().range_size(), attempts, elapsed),
+                RoundEnd::Lost | RoundEnd::TimedOut => 0,
+            },
+        };
+        self.results.push(result);
+
+        let session_over =
+            matches!(self.limit, Some(TimeLimit::PerSession(_))) && self.time_is_up();
+        if self.results.len() as u32 >= self.rounds || session_over {
+            self.finished = true;
+        } else {
+            self.game.next_round();
+            self.round_started = now;
+        }
+
+        result
+    }
+}
+
+/// Final table of a session, one line per round and the total.
+pub struct ScoreTable<'a>(pub &'a [RoundResult]);
+
+impl fmt::Display for ScoreTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>5}  {:>12}  {:>8}  {:>8}  {:<8}  {:>6}",
+            "Round", "Secret", "Attempts", "Time", "Result", "Score"
+        )?;
+        for result in self.0 {
+            writeln!(
+                f,
+                "{:>5}  {:>12}  {:>8}  {:>7.1}s  {:<8}  {:>6}",
+                result.round,
+                result.secret,
+                result.attempts,
+                result.elapsed.as_secs_f64(),
+                result.end.to_string(),
+                result.score
+            )?;
+        }
+        let total: u64 = self.0.iter().map(|result| result.score).sum();
+        writeln!(f, "{:>5}  {:>50}", "Total", total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Difficulty;
+
+    fn session(rounds: u32, limit: TimeLimit, clock: &ManualClock) -> TimedSession<&ManualClock> {
+        let config = Config::new(Difficulty::Custom, 1, 100, None).unwrap();
+        TimedSession::new(Game::with_secret(config, 42), rounds, Some(limit), clock)
+    }
+
+    #[test]
+    fn each_round_gets_its_own_time() {
+        let clock = ManualClock::new();
+        let mut session = session(2, TimeLimit::PerRound(Duration::from_secs(10)), &clock);
+
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(session.guess(1), Progress::Feedback(Outcome::TooSmall));
+        assert_eq!(session.expire(), None);
+        assert_eq!(session.time_left(), Some(Duration::from_secs(5)));
+
+        clock.advance(Duration::from_secs(6));
+        // Too late, the guess is not counted and the round ends
+        let Progress::RoundOver(result) = session.guess(42) else {
+            panic!("the round should be over");
+        };
+        assert_eq!(result.end, RoundEnd::TimedOut);
+        assert_eq!(result.attempts, 1);
+        assert_eq!(result.elapsed, Duration::from_secs(11));
+        assert_eq!(result.score, 0);
+
+        assert!(!session.is_finished());
+        assert_eq!(session.round(), 2);
+        assert_eq!(session.time_left(), Some(Duration::from_secs(10)));
+
+        clock.advance(Duration::from_secs(3));
+        let secret = session.game().secret();
+        let Progress::RoundOver(result) = session.guess(secret) else {
+            panic!("the secret should win");
+        };
+        assert_eq!(result.end, RoundEnd::Won);
+        assert_eq!(result.elapsed, Duration::from_secs(3));
+        assert!(session.is_finished());
+    }
+
+    #[test]
+    fn running_out_of_session_time_ends_the_session() {
+        let clock = ManualClock::new();
+        let mut session = session(5, TimeLimit::PerSession(Duration::from_secs(30)), &clock);
+
+        clock.advance(Duration::from_secs(10));
+        let Progress::RoundOver(won) = session.guess(42) else {
+            panic!("the secret should win");
+        };
+        assert_eq!(won.score, score(100, 1, Duration::from_secs(10)));
+        assert!(!session.is_finished());
+        assert_eq!(session.time_left(), Some(Duration::from_secs(20)));
+
+        clock.advance(Duration::from_secs(25));
+        let timed_out = session.expire().unwrap();
+        assert_eq!(timed_out.round, 2);
+        assert_eq!(timed_out.end, RoundEnd::TimedOut);
+        assert!(session.is_finished());
+        assert_eq!(session.expire(), None);
+        assert_eq!(session.results().len(), 2);
+        assert_eq!(session.total_score(), won.score);
+    }
+
+    #[test]
+    fn score_rewards_few_attempts_and_little_time() {
+        // log2(100) = 6.64 and binary search needs 7 guesses for 100 numbers
+        assert_eq!(score(100, 7, Duration::ZERO), 664);
+        assert_eq!(score(100, 1, Duration::ZERO), 664);
+        assert_eq!(score(100, 14, Duration::ZERO), 332);
+        assert_eq!(score(100, 7, Duration::from_secs(60)), 332);
+        assert_eq!(score(1, 1, Duration::ZERO), 100);
+    }
+}
@@ -0,0 +1,542 @@
+/*
+ * Hot-seat tournament, several named players taking turns on one terminal.
+ *
+ * Rules:
+ *  - Shared mode: everybody hunts the same secret, one guess per turn, the first to hit it
+ *    wins the round. Separate mode: every player plays a whole round on an own secret,
+ *    fewest attempts wins.
+ *  - Every player has an own attempt cap per round, the configured limit or twice what
+ *    binary search needs if there is none. A player at the cap is out of the round.
+ *  - A player may forfeit on their turn, that is a lost round for them only.
+ *  - A win is worth 3 points. Players tied for fewest attempts in separate mode share
+ *    a draw worth 1 point each. Nobody scores if nobody finds the number.
+ *  - Who goes first rotates every round.
+ *  - Standings: points, then wins, then fewest attempts per solved round, a player who
+ *    solved nothing comes last. Players equal on all three share a rank.
+ */
+
+use crate::{
+    config::Config,
+    game::{Game, Outcome},
+    strategy,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{cmp::Ordering, collections::HashSet, fmt};
+
+const WIN_POINTS: u32 = 3;
+const DRAW_POINTS: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Everybody on one secret, one guess per turn.
+    Shared,
+    /// Everybody on an own secret, one whole round per turn.
+    Separate,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TournamentError {
+    TooFewPlayers,
+    EmptyName,
+    DuplicateName(String),
+    ZeroRounds,
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TournamentError::TooFewPlayers => write!(f, "A tournament needs at least 2 players"),
+            TournamentError::EmptyName => write!(f, "Player names must not be empty"),
+            TournamentError::DuplicateName(name) => {
+                write!(f, "There are two players called {name}")
+            }
+            TournamentError::ZeroRounds => write!(f, "A tournament needs at least 1 round"),
+        }
+    }
+}
+
+impl std::error::Error for TournamentError {}
+
+/// Where a player is in the current round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Playing,
+    Found { attempts: u32 },
+    OutOfAttempts,
+    Forfeited,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub points: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub solved: u32,
+    /// Attempts over the solved rounds only.
+    pub attempts: u32,
+    pub forfeits: u32,
+}
+
+impl Standing {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            points: 0,
+            wins: 0,
+            draws: 0,
+            solved: 0,
+            attempts: 0,
+            forfeits: 0,
+        }
+    }
+
+    /// Best first, players that compare equal share a rank.
+    fn rank_cmp(&self, other: &Self) -> Ordering {
+        other
+            .points
+            .cmp(&self.points)
+            .then(other.wins.cmp(&self.wins))
+            .then_with(|| match (self.solved, other.solved) {
+                (0, 0) => Ordering::Equal,
+                (0, _) => Ordering::Greater,
+                (_, 0) => Ordering::Less,
+                // attempts / solved against each other, without the rounding
+                (solved, other_solved) => (u64::from(self.attempts) * u64::from(other_solved))
+                    .cmp(&(u64::from(other.attempts) * u64::from(solved))),
+            })
+    }
+}
+
+/// How one player did in a finished round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerRound {
+    pub name: String,
+    pub secret: i128,
+    pub status: Status,
+    /// Counted guesses, whether or not they found the number.
+    pub attempts: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundSummary {
+    pub round: u32,
+    pub players: Vec<PlayerRound>,
+    /// One name for a win, several for a draw, none if nobody found it.
+    pub winners: Vec<String>,
+}
+
+/// Whose turn it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Turn<'a> {
+    pub round: u32,
+    pub player: &'a str,
+    pub attempts_left: Option<u32>,
+}
+
+#[derive(Debug)]
+struct Round {
+    games: Vec<Game>,
+    status: Vec<Status>,
+    current: usize,
+}
+
+#[derive(Debug)]
+pub struct Tournament {
+    config: Config,
+    mode: Mode,
+    rounds: u32,
+    round: u32,
+    standings: Vec<Standing>,
+    current: Option<Round>,
+    rng: ChaCha8Rng,
+}
+
+impl Tournament {
+    pub fn new(
+        names: Vec<String>,
+        config: Config,
+        mode: Mode,
+        rounds: u32,
+        seed: Option<u64>,
+    ) -> Result<Self, TournamentError> {
+        if names.len() < 2 {
+            return Err(TournamentError::TooFewPlayers);
+        }
+        if rounds == 0 {
+            return Err(TournamentError::ZeroRounds);
+        }
+        let mut seen = HashSet::new();
+        for name in &names {
+            if name.trim().is_empty() {
+                return Err(TournamentError::EmptyName);
+            }
+            if !seen.insert(name.as_str()) {
+                return Err(TournamentError::DuplicateName(name.clone()));
+            }
+        }
+
+        let cap = config
+            .max_attempts()
+            .unwrap_or_else(|| 2 * strategy::optimal_worst_case(config.range_size()));
+        let config = config
+            .with_max_attempts(Some(cap.max(1)))
+            .expect("the cap is at least 1");
+
+        let mut tournament = Self {
+            config,
+            mode,
+            rounds,
+            round: 0,
+            standings: names.into_iter().map(Standing::new).collect(),
+            current: None,
+            rng: match seed {
+                Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+                None => ChaCha8Rng::from_entropy(),
+            },
+        };
+        tournament.start_round();
+        Ok(tournament)
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// `None` once every round has been played.
+    pub fn turn(&self) -> Option<Turn<'_>> {
+        let round = self.current.as_ref()?;
+        Some(Turn {
+            round: self.round,
+            player: &self.standings[round.current].name,
+            attempts_left: round.games[round.current].attempts_left(),
+        })
+    }
+
+    /// A guess by the player whose turn it is, the summary comes with the last guess of a round.
+    pub fn guess(&mut self, guess: i128) -> (Outcome, Option<RoundSummary>) {
+        let Some(round) = self.current.as_mut() else {
+            return (Outcome::Invalid, None);
+        };
+
+        let player = round.current;
+        let outcome = round.games[player].check(guess);
+        match outcome {
+            Outcome::Won { attempts } => round.status[player] = Status::Found { attempts },
+            Outcome::Lost { .. } => round.status[player] = Status::OutOfAttempts,
+            _ => {}
+        }
+
+        (outcome, self.advance())
+    }
+
+    /// Gives up the current round for the player whose turn it is.
+    pub fn forfeit(&mut self) -> Option<RoundSummary> {
+        let round = self.current.as_mut()?;
+        round.status[round.current] = Status::Forfeited;
+        self.advance()
+    }
+
+    /// Best first, see the rules at the top for ties.
+    pub fn standings(&self) -> Vec<(usize, &Standing)> {
+        let mut standings: Vec<&Standing> = self.standings.iter().collect();
+        standings.sort_by(|a, b| a.rank_cmp(b).then(a.name.cmp(&b.name)));
+
+        let mut ranked: Vec<(usize, &Standing)> = Vec::with_capacity(standings.len());
+        for (index, standing) in standings.iter().enumerate() {
+            let rank = match ranked.last() {
+                Some(&(rank, previous)) if previous.rank_cmp(standing) == Ordering::Equal => rank,
+                _ => index + 1,
+            };
+            ranked.push((rank, *standing));
+        }
+        ranked
+    }
+
+    fn start_round(&mut self) {
+        self.round += 1;
+        let players = self.standings.len();
+        let (min, max) = (self.config.min(), self.config.max());
+
+        let shared = self.rng.gen_range(min..=max);
+        let games = (0..players)
+            .map(|_| {
+                let secret = match self.mode {
+                    Mode::Shared => shared,
+                    Mode::Separate => self.rng.gen_range(min..=max),
+                };
+                Game::with_secret(self.config, secret)
+            })
+            .collect();
+
+        self.current = Some(Round {
+            games,
+            status: vec![Status::Playing; players],
+            current: (self.round as usize - 1) % players,
+        });
+    }
+
+    /// Moves the turn on, or closes the round when it is decided.
+    fn advance(&mut self) -> Option<RoundSummary> {
+        let round = self.current.as_mut()?;
+        let players = round.status.len();
+
+        let decided = match self.mode {
+            Mode::Shared => round
+                .status
+                .iter()
+                .any(|status| matches!(status, Status::Found { .. })),
+            Mode::Separate => false,
+        };
+        // Shared mode hands over after every guess, separate mode only when a player is done
+        let next = (0..players)
+            .map(|offset| {
+                let start = match self.mode {
+                    Mode::Shared => round.current + 1,
+                    Mode::Separate => round.current,
+                };
+                (start + offset) % players
+            })
+            .find(|&player| round.status[player] == Status::Playing);
+
+        match next {
+            Some(next) if !decided => {
+                round.current = next;
+                None
+            }
+            _ => Some(self.finish_round()),
+        }
+    }
+
+    fn finish_round(&mut self) -> RoundSummary {
+        let round = self.current.take().expect("a round is running");
+
+        let best = round
+            .status
+            .iter()
+            .filter_map(|status| match status {
+                Status::Found { attempts } => Some(*attempts),
+                _ => None,
+            })
+            .min();
+        let winners: Vec<usize> = (0..round.status.len())
+            .filter(|&player| {
+                matches!(round.status[player], Status::Found { attempts } if Some(attempts) == best)
+            })
+            .collect();
+
+        for (player, status) in round.status.iter().enumerate() {
+            let standing = &mut self.standings[player];
+            match status {
+                Status::Found { attempts } => {
+                    standing.solved += 1;
+                    standing.attempts += attempts;
+                }
+                Status::Forfeited => standing.forfeits += 1,
+                Status::Playing | Status::OutOfAttempts => {}
+            }
+        }
+        for &player in &winners {
+            let standing = &mut self.standings[player];
+            if winners.len() == 1 {
+                standing.wins += 1;
+                standing.points += WIN_POINTS;
+            } else {
+                standing.draws += 1;
+                standing.points += DRAW_POINTS;
+            }
+        }
+
+        let summary = RoundSummary {
+            round: self.round,
+            players: round
+                .status
+                .iter()
+                .zip(&round.games)
+                .zip(&self.standings)
+                .map(|((status, game), standing)| PlayerRound {
+                    name: standing.name.clone(),
+                    secret: game.secret(),
+                    status: *status,
+                    attempts: game.attempts(),
+                })
+                .collect(),
+            winners: winners
+                .into_iter()
+                .map(|player| self.standings[player].name.clone())
+                .collect(),
+        };
+
+        if self.round < self.rounds {
+            self.start_round();
+        }
+        summary
+    }
+}
+
+pub struct StandingsTable<'a>(pub &'a [(usize, &'a Standing)]);
+
+impl fmt::Display for StandingsTable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|(_, standing)| standing.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(6);
+
+        writeln!(
+            f,
+            "{:>4}  {:<width$}  {:>6}  {:>4}  {:>5}  {:>6}  {:>8}  {:>8}",
+            "Rank", "Player", "Points", "Wins", "Draws", "Solved", "Attempts", "Forfeits"
+        )?;
+        for (rank, standing) in self.0 {
+            writeln!(
+                f,
+                "{:>4}  {:<width$}  {:>6}  {:>4}  {:>5}  {:>6}  {:>8}  {:>8}",
+                format!("{rank}."),
+                standing.name,
+                standing.points,
+                standing.wins,
+                standing.draws,
+                standing.solved,
+                standing.attempts,
+                standing.forfeits
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Difficulty;
+
+    fn tournament(mode: Mode, max_attempts: Option<u32>) -> Tournament {
+        let config = Config::new(Difficulty::Custom, 1, 100, max_attempts).unwrap();
+        let names = vec![String::from("Ada"), String::from("Bob")];
+        Tournament::new(names, config, mode, 3, Some(7)).unwrap()
+    }
+
+    fn secret(tournament: &Tournament, player: usize) -> i128 {
+        tournament.current.as_ref().unwrap().games[player].secret()
+    }
+
+    /// A guess that misses `secret` but stays in range.
+    fn miss(secret: i128) -> i128 {
+        if secret == 1 {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn standing(name: &str, points: u32, wins: u32, solved: u32, attempts: u32) -> Standing {
+        Standing {
+            points,
+            wins,
+            solved,
+            attempts,
+            ..Standing::new(String::from(name))
+        }
+    }
+
+    #[test]
+    fn without_a_limit_the_cap_is_twice_binary_search() {
+        assert_eq!(
+            tournament(Mode::Shared, None).config().max_attempts(),
+            Some(2 * strategy::optimal_worst_case(100))
+        );
+        assert_eq!(
+            tournament(Mode::Shared, Some(5)).config().max_attempts(),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn the_first_to_find_the_shared_number_wins_and_the_start_rotates() {
+        let mut tournament = tournament(Mode::Shared, None);
+        assert_eq!(tournament.turn().unwrap().player, "Ada");
+        let number = secret(&tournament, 0);
+        assert_eq!(secret(&tournament, 1), number);
+
+        assert_eq!(tournament.guess(miss(number)).1, None);
+        assert_eq!(tournament.turn().unwrap().player, "Bob");
+        let summary = tournament.guess(number).1.unwrap();
+        assert_eq!(summary.winners, ["Bob"]);
+        assert_eq!(summary.players[0].status, Status::Playing);
+
+        let turn = tournament.turn().unwrap();
+        assert_eq!((turn.round, turn.player), (2, "Bob"));
+        let bob = &tournament.standings()[0].1;
+        assert_eq!(
+            (bob.name.as_str(), bob.points, bob.wins),
+            ("Bob", WIN_POINTS, 1)
+        );
+    }
+
+    #[test]
+    fn equal_attempts_in_separate_rounds_are_a_draw() {
+        let mut tournament = tournament(Mode::Separate, None);
+        let number = secret(&tournament, 0);
+        assert_eq!(tournament.guess(number).1, None);
+        let number = secret(&tournament, 1);
+        let summary = tournament.guess(number).1.unwrap();
+        assert_eq!(summary.winners, ["Ada", "Bob"]);
+
+        for (rank, standing) in tournament.standings() {
+            assert_eq!(rank, 1);
+            assert_eq!((standing.points, standing.draws), (DRAW_POINTS, 1));
+        }
+    }
+
+    #[test]
+    fn a_forfeit_loses_the_round_for_that_player_only() {
+        let mut tournament = tournament(Mode::Separate, None);
+        assert_eq!(tournament.forfeit(), None);
+        let number = secret(&tournament, 1);
+        let summary = tournament.guess(number).1.unwrap();
+        assert_eq!(summary.players[0].status, Status::Forfeited);
+        assert_eq!(summary.winners, ["Bob"]);
+    }
+
+    #[test]
+    fn ties_go_by_attempts_per_solved_round() {
+        let tournament = Tournament {
+            standings: vec![
+                // 12 attempts over 3 rounds beats 9 over 2
+                standing("Ada", 3, 1, 2, 9),
+                standing("Bob", 3, 1, 3, 12),
+                standing("Cy", 3, 1, 1, 4),
+                standing("Dee", 3, 1, 0, 0),
+                standing("Eve", 6, 2, 2, 20),
+            ],
+            ..tournament(Mode::Shared, None)
+        };
+
+        let ranked: Vec<(usize, &str)> = tournament
+            .standings()
+            .into_iter()
+            .map(|(rank, standing)| (rank, standing.name.as_str()))
+            .collect();
+        assert_eq!(
+            ranked,
+            [(1, "Eve"), (2, "Bob"), (2, "Cy"), (4, "Ada"), (5, "Dee")]
+        );
+    }
+}
@@ -0,0 +1,249 @@
+/*
+ * Full screen terminal UI for a round, drawn with crossterm.
+ * It shows what is still possible as a shrinking bar, the guesses so far, the attempts and a timer.
+ * The line mode in main.rs stays the default so the game can still be piped.
+ */
+
+use crate::{
+    game::{Game, Outcome},
+    i18n::{self, Lang, Text},
+    parse::parse_guess,
+    strategy::Interval,
+};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+use std::{
+    cmp::Ordering,
+    io::{self, Stdout, Write},
+    time::{Duration, Instant},
+};
+
+const BAR_WIDTH: usize = 50;
+const HISTORY_ROWS: usize = 10;
+
+/// Puts the terminal back to normal when dropped, even if drawing failed half way.
+struct TerminalGuard {
+    stdout: Stdout,
+}
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self { stdout })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+struct Screen<'a> {
+    game: &'a Game,
+    interval: Interval,
+    history: &'a [(i128, Ordering)],
+    input: &'a str,
+    message: &'a str,
+    commitment: &'a str,
+    lang: Lang,
+    elapsed: Duration,
+}
+
+/// Plays the current round of `game` full screen.
+/// Returns the final `Won` or `Lost` outcome, or `None` if the player quit with Esc or Ctrl-C.
+/// `commitment` stays on screen, revealing the secret is up to the caller once the screen is gone.
+pub fn run(game: &mut Game, commitment: &str, lang: Lang) -> io::Result<Option<Outcome>> {
+    let mut guard = TerminalGuard::enter()?;
+    let started = Instant::now();
+    let mut interval = Interval::from_config(game.config());
+    let mut history = Vec::new();
+    let mut input = String::new();
+    let mut message = lang.text(Text::ScreenHelp);
+    let mut finished = None;
+
+    loop {
+        draw(
+            &mut guard.stdout,
+            &Screen {
+                game,
+                interval,
+                history: &history,
+                input: &input,
+                message: &message,
+                commitment,
+                lang,
+                elapsed: started.elapsed(),
+            },
+        )?;
+
+        // Redraw at least every quarter second so the timer keeps ticking
+        if !event::poll(Duration::from_millis(250))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if finished.is_some() {
+            // Any key leaves the final screen
+            return Ok(finished);
+        }
+
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(None),
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                let parsed = parse_guess(&input);
+                input.clear();
+
+                let guess = match parsed {
+                    Ok(guess) => guess,
+                    Err(e) => {
+                        message = lang.text(Text::Invalid(&e));
+                        continue;
+                    }
+                };
+
+                let outcome = game.check(guess);
+                let ordering = game.compare(guess);
+                interval.narrow(guess, ordering);
+                history.push((guess, ordering));
+
+                message = match outcome {
+                    // `check` only refuses guesses after the round, and we stop taking them then
+                    Outcome::Invalid => String::new(),
+                    Outcome::TooSmall => lang.text(Text::TooSmall),
+                    Outcome::TooBig => lang.text(Text::TooBig),
+                    Outcome::Won { attempts } => format!(
+                        "{} {}",
+                        lang.text(Text::Won { attempts }),
+                        lang.text(Text::PressAnyKey)
+                    ),
+                    Outcome::Lost { secret } => format!(
+                        "{} {}",
+                        lang.text(Text::Lost { secret }),
+                        lang.text(Text::PressAnyKey)
+                    ),
+                };
+                if game.is_over() {
+                    finished = Some(outcome);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn draw(stdout: &mut Stdout, screen: &Screen) -> io::Result<()> {
+    let config = screen.game.config();
+    let lang = screen.lang;
+
+    queue!(
+        stdout,
+        terminal::Clear(ClearType::All),
+        cursor::MoveTo(0, 0),
+        Print(lang.text(Text::GuessTheNumber)),
+        cursor::MoveTo(0, 1),
+        Print(lang.text(Text::Range {
+            difficulty: config.difficulty(),
+            min: config.min(),
+            max: config.max(),
+        })),
+        cursor::MoveTo(0, 2),
+        Print(lang.text(Text::Attempts {
+            attempts: screen.game.attempts(),
+            max: config.max_attempts(),
+        })),
+        Print("   "),
+        Print(lang.text(Text::Time {
+            seconds: screen.elapsed.as_secs(),
+        })),
+        cursor::MoveTo(0, 3),
+        Print(lang.text(Text::Commitment(screen.commitment))),
+        cursor::MoveTo(0, 4),
+    )?;
+
+    // The bar covers the whole range, the highlighted part is what is still possible
+    let (start, end) = bar_span(screen.interval, config.min(), config.range_size());
+    queue!(
+        stdout,
+        Print("["),
+        Print("·".repeat(start)),
+        SetForegroundColor(Color::Green),
+        Print("█".repeat(end - start)),
+        ResetColor,
+        Print("·".repeat(BAR_WIDTH - end)),
+        Print("]"),
+        cursor::MoveTo(0, 5),
+        Print(lang.text(Text::StillPossible {
+            low: screen.interval.low,
+            high: screen.interval.high,
+            count: screen.interval.len(),
+        })),
+        cursor::MoveTo(0, 7),
+        Print(lang.text(Text::History)),
+    )?;
+
+    let skipped = screen.history.len().saturating_sub(HISTORY_ROWS);
+    for (row, (guess, ordering)) in screen.history.iter().skip(skipped).enumerate() {
+        let color = match ordering {
+            Ordering::Less => Color::Blue,
+            Ordering::Greater => Color::Red,
+            Ordering::Equal => Color::Green,
+        };
+        queue!(
+            stdout,
+            cursor::MoveTo(2, 8 + row as u16),
+            Print(format!("#{:<3} {guess:>12}  ", skipped + row + 1)),
+            SetForegroundColor(color),
+            Print(lang.text(i18n::ordering_text(*ordering))),
+            ResetColor,
+        )?;
+    }
+
+    let bottom = 9 + HISTORY_ROWS as u16;
+    queue!(
+        stdout,
+        cursor::MoveTo(0, bottom),
+        Print(screen.message),
+        cursor::MoveTo(0, bottom + 1),
+        Print(format!("> {}_", screen.input)),
+    )?;
+
+    stdout.flush()
+}
+
+/// Start and end column of the still possible part of the range on the bar.
+fn bar_span(interval: Interval, min: i128, range_size: u128) -> (usize, usize) {
+    if interval.is_empty() {
+        return (0, 0);
+    }
+
+    let column = |offset: u128| {
+        let ratio = offset as f64 / range_size as f64;
+        ((ratio * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH)
+    };
+    let start = column(interval.low.abs_diff(min));
+    // Always show at least one block so a single remaining number stays visible
+    let end = column(interval.high.abs_diff(min) + 1)
+        .max(start + 1)
+        .min(BAR_WIDTH);
+
+    (start.min(end), end)
+}